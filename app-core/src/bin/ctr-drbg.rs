@@ -0,0 +1,33 @@
+#![no_std]
+#![no_main]
+
+use app_core::{Cracen, CtrDrbg};
+use cortex_m_rt::entry;
+use defmt::info;
+use defmt_rtt as _;
+use panic_probe as _;
+
+#[entry]
+fn main() -> ! {
+    info!("Starting nRF54L15 CTR_DRBG example...");
+    let p = nrf54l15_app_pac::Peripherals::take().unwrap();
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
+
+    // Seeded from the TRNG. The CAVP known-answer tests run on the host, in
+    // `drbg.rs`.
+    let mut drbg = CtrDrbg::instantiate(&mut cracen, b"app-core example", false).unwrap();
+    let mut buf = [0u8; 64];
+    drbg.generate(&mut cracen, &mut buf, &[]).unwrap();
+    info!("buf: {:02x}", buf);
+    drbg.generate(&mut cracen, &mut buf, b"more").unwrap();
+    info!("buf: {:02x}", buf);
+    defmt::assert_eq!(drbg.reseed_counter(), 3);
+
+    let mut drbg = CtrDrbg::instantiate(&mut cracen, &[], true).unwrap();
+    drbg.generate(&mut cracen, &mut buf, &[]).unwrap();
+    info!("prediction resistant buf: {:02x}", buf);
+
+    loop {
+        cortex_m::asm::nop();
+    }
+}
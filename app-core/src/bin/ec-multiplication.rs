@@ -1,29 +1,17 @@
 #![no_std]
 #![no_main]
 
+use app_core::Cracen;
 use cortex_m_rt::entry;
 use defmt::info;
 use defmt_rtt as _;
-use nrf54l15_app_pac;
 use panic_probe as _;
 
 #[entry]
 fn main() -> ! {
     info!("Starting nRF54L15 EC-multiplication example...");
     let p = nrf54l15_app_pac::Peripherals::take().unwrap();
-
-    p.global_cracen_s.enable().write(|w| {
-        w.rng().set_bit();
-        w.cryptomaster().set_bit();
-        w.pkeikg().set_bit()
-    });
-
-    unsafe { app_core::load_microcode() };
-
-    let cracen = &p.global_cracencore_s;
-
-    while cracen.pk().status().read().pkbusy().bit_is_set() {}
-    while cracen.ikg().status().read().ctrdrbgbusy().bit_is_set() {}
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
 
     let mut out_x = [0u8; 32];
     let mut out_y = [0u8; 32];
@@ -47,7 +35,9 @@ fn main() -> ! {
         0x50, 0x32,
     ];
 
-    app_core::cracen_ec_scalar_mul(&p, &scalar, &pub_key_x, &pub_key_y, &mut out_x, &mut out_y);
+    cracen
+        .ec_scalar_mul(&scalar, &pub_key_x, &pub_key_y, &mut out_x, &mut out_y)
+        .unwrap();
 
     info!("Result X = {:02x}", out_x);
     info!("Result Y = {:02x}", out_y);
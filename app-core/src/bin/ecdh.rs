@@ -0,0 +1,110 @@
+#![no_std]
+#![no_main]
+
+use app_core::{Cracen, CracenError};
+use cortex_m_rt::entry;
+use defmt::info;
+use defmt_rtt as _;
+use panic_probe as _;
+
+// RFC 5903 8.1: both key pairs and the shared x-coordinate
+const I: [u8; 32] = [
+    0xc8, 0x8f, 0x01, 0xf5, 0x10, 0xd9, 0xac, 0x3f, 0x70, 0xa2, 0x92, 0xda, 0xa2, 0x31, 0x6d, 0xe5,
+    0x44, 0xe9, 0xaa, 0xb8, 0xaf, 0xe8, 0x40, 0x49, 0xc6, 0x2a, 0x9c, 0x57, 0x86, 0x2d, 0x14, 0x33,
+];
+const GIX: [u8; 32] = [
+    0xda, 0xd0, 0xb6, 0x53, 0x94, 0x22, 0x1c, 0xf9, 0xb0, 0x51, 0xe1, 0xfe, 0xca, 0x57, 0x87, 0xd0,
+    0x98, 0xdf, 0xe6, 0x37, 0xfc, 0x90, 0xb9, 0xef, 0x94, 0x5d, 0x0c, 0x37, 0x72, 0x58, 0x11, 0x80,
+];
+const GIY: [u8; 32] = [
+    0x52, 0x71, 0xa0, 0x46, 0x1c, 0xdb, 0x82, 0x52, 0xd6, 0x1f, 0x1c, 0x45, 0x6f, 0xa3, 0xe5, 0x9a,
+    0xb1, 0xf4, 0x5b, 0x33, 0xac, 0xcf, 0x5f, 0x58, 0x38, 0x9e, 0x05, 0x77, 0xb8, 0x99, 0x0b, 0xb3,
+];
+const R: [u8; 32] = [
+    0xc6, 0xef, 0x9c, 0x5d, 0x78, 0xae, 0x01, 0x2a, 0x01, 0x11, 0x64, 0xac, 0xb3, 0x97, 0xce, 0x20,
+    0x88, 0x68, 0x5d, 0x8f, 0x06, 0xbf, 0x9b, 0xe0, 0xb2, 0x83, 0xab, 0x46, 0x47, 0x6b, 0xee, 0x53,
+];
+const GRX: [u8; 32] = [
+    0xd1, 0x2d, 0xfb, 0x52, 0x89, 0xc8, 0xd4, 0xf8, 0x12, 0x08, 0xb7, 0x02, 0x70, 0x39, 0x8c, 0x34,
+    0x22, 0x96, 0x97, 0x0a, 0x0b, 0xcc, 0xb7, 0x4c, 0x73, 0x6f, 0xc7, 0x55, 0x44, 0x94, 0xbf, 0x63,
+];
+const GRY: [u8; 32] = [
+    0x56, 0xfb, 0xf3, 0xca, 0x36, 0x6c, 0xc2, 0x3e, 0x81, 0x57, 0x85, 0x4c, 0x13, 0xc5, 0x8d, 0x6a,
+    0xac, 0x23, 0xf0, 0x46, 0xad, 0xa3, 0x0f, 0x83, 0x53, 0xe7, 0x4f, 0x33, 0x03, 0x98, 0x72, 0xab,
+];
+const GIRX: [u8; 32] = [
+    0xd6, 0x84, 0x0f, 0x6b, 0x42, 0xf6, 0xed, 0xaf, 0xd1, 0x31, 0x16, 0xe0, 0xe1, 0x25, 0x65, 0x20,
+    0x2f, 0xef, 0x8e, 0x9e, 0xce, 0x7d, 0xce, 0x03, 0x81, 0x24, 0x64, 0xd0, 0x4b, 0x94, 0x42, 0xde,
+];
+const P: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+];
+
+// (6, y) has order 3 on y^2 = x^3 - 3x + b' for
+// b' = 0x1fffffffe00000002000000000000000000000001fffffffffffffffffffffd3
+const X_ORDER_3: [u8; 32] = {
+    let mut x = [0u8; 32];
+    x[31] = 6;
+    x
+};
+const Y_ORDER_3: [u8; 32] = [
+    0x80, 0x06, 0xbe, 0x78, 0xd0, 0x90, 0xab, 0xd1, 0xa7, 0x89, 0x0a, 0x1f, 0x21, 0x17, 0x25, 0x15,
+    0x71, 0x9d, 0xef, 0x90, 0xba, 0xd7, 0xa1, 0x71, 0x3f, 0xc1, 0xe5, 0x8c, 0xbc, 0x12, 0xae, 0xef,
+];
+
+#[entry]
+fn main() -> ! {
+    info!("Starting nRF54L15 ECDH example...");
+    let p = nrf54l15_app_pac::Peripherals::take().unwrap();
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
+
+    let secret = cracen.ecdh_p256(&I, &GRX, &GRY).unwrap();
+    assert_eq!(secret.as_bytes(), &GIRX);
+    let secret = cracen.ecdh_p256(&R, &GIX, &GIY).unwrap();
+    assert_eq!(secret.as_bytes(), &GIRX);
+    info!("RFC 5903 shared secret OK");
+
+    // Invalid public keys, built by hand after Wycheproof's ECDH categories
+    // since its vector files are not bundled here: small-order points on
+    // other curves y^2 = x^3 - 3x + b', a point off the curve, and
+    // coordinates that are not reduced modulo p. All must be refused before
+    // the private key is used.
+    let mut gry_flipped = GRY;
+    gry_flipped[31] ^= 1;
+    let invalid: [(&str, [u8; 32], [u8; 32]); 7] = [
+        // Order 2 on the curve with b' = 0, and how some encoders write the
+        // point at infinity
+        ("(0, 0)", [0; 32], [0; 32]),
+        // Order 2 on the curve with b' = 3 GRX - GRX^3
+        ("(GRX, 0)", GRX, [0; 32]),
+        // Order 3 on the curve with b' below
+        ("order 3 on b'", X_ORDER_3, Y_ORDER_3),
+        ("y with its low bit flipped", GRX, gry_flipped),
+        ("x = p", P, GRY),
+        ("y = p", GRX, P),
+        ("x = 2^256 - 1", [0xff; 32], GRY),
+    ];
+    for (name, x, y) in invalid {
+        assert!(matches!(
+            cracen.ecdh_p256(&I, &x, &y),
+            Err(CracenError::InvalidInput)
+        ));
+        info!("Rejected {}", name);
+    }
+
+    // Private keys outside [1, n-1]
+    assert!(matches!(
+        cracen.ecdh_p256(&[0; 32], &GRX, &GRY),
+        Err(CracenError::InvalidInput)
+    ));
+    assert!(matches!(
+        cracen.ecdh_p256(&[0xff; 32], &GRX, &GRY),
+        Err(CracenError::InvalidInput)
+    ));
+    info!("Rejected out-of-range private keys");
+
+    loop {
+        cortex_m::asm::nop();
+    }
+}
@@ -0,0 +1,968 @@
+#![no_std]
+#![no_main]
+
+use app_core::{Cracen, CracenError};
+use cortex_m_rt::entry;
+use defmt::info;
+use defmt_rtt as _;
+use panic_probe as _;
+
+// Base point
+const P384_GX: [u8; 48] = [
+    0xAA, 0x87, 0xCA, 0x22, 0xBE, 0x8B, 0x05, 0x37, 0x8E, 0xB1, 0xC7, 0x1E, 0xF3, 0x20, 0xAD, 0x74,
+    0x6E, 0x1D, 0x3B, 0x62, 0x8B, 0xA7, 0x9B, 0x98, 0x59, 0xF7, 0x41, 0xE0, 0x82, 0x54, 0x2A, 0x38,
+    0x55, 0x02, 0xF2, 0x5D, 0xBF, 0x55, 0x29, 0x6C, 0x3A, 0x54, 0x5E, 0x38, 0x72, 0x76, 0x0A, 0xB7,
+];
+const P384_GY: [u8; 48] = [
+    0x36, 0x17, 0xDE, 0x4A, 0x96, 0x26, 0x2C, 0x6F, 0x5D, 0x9E, 0x98, 0xBF, 0x92, 0x92, 0xDC, 0x29,
+    0xF8, 0xF4, 0x1D, 0xBD, 0x28, 0x9A, 0x14, 0x7C, 0xE9, 0xDA, 0x31, 0x13, 0xB5, 0xF0, 0xB8, 0xC0,
+    0x0A, 0x60, 0xB1, 0xCE, 0x1D, 0x7E, 0x81, 0x9D, 0x7A, 0x43, 0x1D, 0x7C, 0x90, 0xEA, 0x0E, 0x5F,
+];
+// RFC 6979 A.2.6: private key x, public key U
+const P384_D: [u8; 48] = [
+    0x6B, 0x9D, 0x3D, 0xAD, 0x2E, 0x1B, 0x8C, 0x1C, 0x05, 0xB1, 0x98, 0x75, 0xB6, 0x65, 0x9F, 0x4D,
+    0xE2, 0x3C, 0x3B, 0x66, 0x7B, 0xF2, 0x97, 0xBA, 0x9A, 0xA4, 0x77, 0x40, 0x78, 0x71, 0x37, 0xD8,
+    0x96, 0xD5, 0x72, 0x4E, 0x4C, 0x70, 0xA8, 0x25, 0xF8, 0x72, 0xC9, 0xEA, 0x60, 0xD2, 0xED, 0xF5,
+];
+const P384_QX: [u8; 48] = [
+    0xEC, 0x3A, 0x4E, 0x41, 0x5B, 0x4E, 0x19, 0xA4, 0x56, 0x86, 0x18, 0x02, 0x9F, 0x42, 0x7F, 0xA5,
+    0xDA, 0x9A, 0x8B, 0xC4, 0xAE, 0x92, 0xE0, 0x2E, 0x06, 0xAA, 0xE5, 0x28, 0x6B, 0x30, 0x0C, 0x64,
+    0xDE, 0xF8, 0xF0, 0xEA, 0x90, 0x55, 0x86, 0x60, 0x64, 0xA2, 0x54, 0x51, 0x54, 0x80, 0xBC, 0x13,
+];
+const P384_QY: [u8; 48] = [
+    0x80, 0x15, 0xD9, 0xB7, 0x2D, 0x7D, 0x57, 0x24, 0x4E, 0xA8, 0xEF, 0x9A, 0xC0, 0xC6, 0x21, 0x89,
+    0x67, 0x08, 0xA5, 0x93, 0x67, 0xF9, 0xDF, 0xB9, 0xF5, 0x4C, 0xA8, 0x4B, 0x3F, 0x1C, 0x9D, 0xB1,
+    0x28, 0x8B, 0x23, 0x1C, 0x3A, 0xE0, 0xD4, 0xFE, 0x73, 0x44, 0xFD, 0x25, 0x33, 0x26, 0x47, 0x20,
+];
+// Signature of "sample" with SHA-384
+const P384_R: [u8; 48] = [
+    0x94, 0xED, 0xBB, 0x92, 0xA5, 0xEC, 0xB8, 0xAA, 0xD4, 0x73, 0x6E, 0x56, 0xC6, 0x91, 0x91, 0x6B,
+    0x3F, 0x88, 0x14, 0x06, 0x66, 0xCE, 0x9F, 0xA7, 0x3D, 0x64, 0xC4, 0xEA, 0x95, 0xAD, 0x13, 0x3C,
+    0x81, 0xA6, 0x48, 0x15, 0x2E, 0x44, 0xAC, 0xF9, 0x6E, 0x36, 0xDD, 0x1E, 0x80, 0xFA, 0xBE, 0x46,
+];
+const P384_S: [u8; 48] = [
+    0x99, 0xEF, 0x4A, 0xEB, 0x15, 0xF1, 0x78, 0xCE, 0xA1, 0xFE, 0x40, 0xDB, 0x26, 0x03, 0x13, 0x8F,
+    0x13, 0x0E, 0x74, 0x0A, 0x19, 0x62, 0x45, 0x26, 0x20, 0x3B, 0x63, 0x51, 0xD0, 0xA3, 0xA9, 0x4F,
+    0xA3, 0x29, 0xC1, 0x45, 0x78, 0x6E, 0x67, 0x9E, 0x7B, 0x82, 0xC7, 0x1A, 0x38, 0x62, 0x8A, 0xC8,
+];
+
+// Base point
+const P521_GX: [u8; 66] = [
+    0x00, 0xC6, 0x85, 0x8E, 0x06, 0xB7, 0x04, 0x04, 0xE9, 0xCD, 0x9E, 0x3E, 0xCB, 0x66, 0x23, 0x95,
+    0xB4, 0x42, 0x9C, 0x64, 0x81, 0x39, 0x05, 0x3F, 0xB5, 0x21, 0xF8, 0x28, 0xAF, 0x60, 0x6B, 0x4D,
+    0x3D, 0xBA, 0xA1, 0x4B, 0x5E, 0x77, 0xEF, 0xE7, 0x59, 0x28, 0xFE, 0x1D, 0xC1, 0x27, 0xA2, 0xFF,
+    0xA8, 0xDE, 0x33, 0x48, 0xB3, 0xC1, 0x85, 0x6A, 0x42, 0x9B, 0xF9, 0x7E, 0x7E, 0x31, 0xC2, 0xE5,
+    0xBD, 0x66,
+];
+const P521_GY: [u8; 66] = [
+    0x01, 0x18, 0x39, 0x29, 0x6A, 0x78, 0x9A, 0x3B, 0xC0, 0x04, 0x5C, 0x8A, 0x5F, 0xB4, 0x2C, 0x7D,
+    0x1B, 0xD9, 0x98, 0xF5, 0x44, 0x49, 0x57, 0x9B, 0x44, 0x68, 0x17, 0xAF, 0xBD, 0x17, 0x27, 0x3E,
+    0x66, 0x2C, 0x97, 0xEE, 0x72, 0x99, 0x5E, 0xF4, 0x26, 0x40, 0xC5, 0x50, 0xB9, 0x01, 0x3F, 0xAD,
+    0x07, 0x61, 0x35, 0x3C, 0x70, 0x86, 0xA2, 0x72, 0xC2, 0x40, 0x88, 0xBE, 0x94, 0x76, 0x9F, 0xD1,
+    0x66, 0x50,
+];
+// RFC 6979 A.2.7: private key x, public key U
+const P521_D: [u8; 66] = [
+    0x00, 0xFA, 0xD0, 0x6D, 0xAA, 0x62, 0xBA, 0x3B, 0x25, 0xD2, 0xFB, 0x40, 0x13, 0x3D, 0xA7, 0x57,
+    0x20, 0x5D, 0xE6, 0x7F, 0x5B, 0xB0, 0x01, 0x8F, 0xEE, 0x8C, 0x86, 0xE1, 0xB6, 0x8C, 0x7E, 0x75,
+    0xCA, 0xA8, 0x96, 0xEB, 0x32, 0xF1, 0xF4, 0x7C, 0x70, 0x85, 0x58, 0x36, 0xA6, 0xD1, 0x6F, 0xCC,
+    0x14, 0x66, 0xF6, 0xD8, 0xFB, 0xEC, 0x67, 0xDB, 0x89, 0xEC, 0x0C, 0x08, 0xB0, 0xE9, 0x96, 0xB8,
+    0x35, 0x38,
+];
+const P521_QX: [u8; 66] = [
+    0x01, 0x89, 0x45, 0x50, 0xD0, 0x78, 0x59, 0x32, 0xE0, 0x0E, 0xAA, 0x23, 0xB6, 0x94, 0xF2, 0x13,
+    0xF8, 0xC3, 0x12, 0x1F, 0x86, 0xDC, 0x97, 0xA0, 0x4E, 0x5A, 0x71, 0x67, 0xDB, 0x4E, 0x5B, 0xCD,
+    0x37, 0x11, 0x23, 0xD4, 0x6E, 0x45, 0xDB, 0x6B, 0x5D, 0x53, 0x70, 0xA7, 0xF2, 0x0F, 0xB6, 0x33,
+    0x15, 0x5D, 0x38, 0xFF, 0xA1, 0x6D, 0x2B, 0xD7, 0x61, 0xDC, 0xAC, 0x47, 0x4B, 0x9A, 0x2F, 0x50,
+    0x23, 0xA4,
+];
+const P521_QY: [u8; 66] = [
+    0x00, 0x49, 0x31, 0x01, 0xC9, 0x62, 0xCD, 0x4D, 0x2F, 0xDD, 0xF7, 0x82, 0x28, 0x5E, 0x64, 0x58,
+    0x41, 0x39, 0xC2, 0xF9, 0x1B, 0x47, 0xF8, 0x7F, 0xF8, 0x23, 0x54, 0xD6, 0x63, 0x0F, 0x74, 0x6A,
+    0x28, 0xA0, 0xDB, 0x25, 0x74, 0x1B, 0x5B, 0x34, 0xA8, 0x28, 0x00, 0x8B, 0x22, 0xAC, 0xC2, 0x3F,
+    0x92, 0x4F, 0xAA, 0xFB, 0xD4, 0xD3, 0x3F, 0x81, 0xEA, 0x66, 0x95, 0x6D, 0xFE, 0xAA, 0x2B, 0xFD,
+    0xFC, 0xF5,
+];
+// Signature of "sample" with SHA-512
+const P521_R: [u8; 66] = [
+    0x00, 0xC3, 0x28, 0xFA, 0xFC, 0xBD, 0x79, 0xDD, 0x77, 0x85, 0x03, 0x70, 0xC4, 0x63, 0x25, 0xD9,
+    0x87, 0xCB, 0x52, 0x55, 0x69, 0xFB, 0x63, 0xC5, 0xD3, 0xBC, 0x53, 0x95, 0x0E, 0x6D, 0x4C, 0x5F,
+    0x17, 0x4E, 0x25, 0xA1, 0xEE, 0x90, 0x17, 0xB5, 0xD4, 0x50, 0x60, 0x6A, 0xDD, 0x15, 0x2B, 0x53,
+    0x49, 0x31, 0xD7, 0xD4, 0xE8, 0x45, 0x5C, 0xC9, 0x1F, 0x9B, 0x15, 0xBF, 0x05, 0xEC, 0x36, 0xE3,
+    0x77, 0xFA,
+];
+const P521_S: [u8; 66] = [
+    0x00, 0x61, 0x7C, 0xCE, 0x7C, 0xF5, 0x06, 0x48, 0x06, 0xC4, 0x67, 0xF6, 0x78, 0xD3, 0xB4, 0x08,
+    0x0D, 0x6F, 0x1C, 0xC5, 0x0A, 0xF2, 0x6C, 0xA2, 0x09, 0x41, 0x73, 0x08, 0x28, 0x1B, 0x68, 0xAF,
+    0x28, 0x26, 0x23, 0xEA, 0xA6, 0x3E, 0x5B, 0x5C, 0x07, 0x23, 0xD8, 0xB8, 0xC3, 0x7F, 0xF0, 0x77,
+    0x7B, 0x1A, 0x20, 0xF8, 0xCC, 0xB1, 0xDC, 0xCC, 0x43, 0x99, 0x7F, 0x1E, 0xE0, 0xE4, 0x4D, 0xA4,
+    0xA6, 0x7A,
+];
+
+// One signature check over a prehashed message. The valid cases and the
+// first faults come from CAVP SigGen vectors (FIPS 186-4, P-384/SHA-384 and
+// P-521/SHA-512), broken in the ways SigVer does: changed message, R, S or
+// key. The rest are Wycheproof ecdsa_secp384r1_sha384 and
+// ecdsa_secp521r1_sha512 cases whose signature decodes to fixed-size r and
+// s, so only the value checks are left to the engine.
+struct SigVer {
+    case: &'static str,
+    qx: &'static str,
+    qy: &'static str,
+    digest: &'static str,
+    r: &'static str,
+    s: &'static str,
+    valid: bool,
+}
+
+// k * G. Small multiples of G and NIST test points from
+// http://point-at-infinity.org/ecc/nisttv, down to k = n - 1.
+struct ScalarMul {
+    k: &'static str,
+    x: &'static str,
+    y: &'static str,
+}
+
+const P384_SIGVER: [SigVer; 18] = [
+    SigVer {
+        case: "CAVP SigGen, valid",
+        qx: "c2b47944fb5de342d03285880177ca5f7d0f2fcad7678cce4229d6e1932fcac1\
+         1bfc3c3e97d942a3c56bf34123013dbf",
+        qy: "37257906a8223866eda0743c519616a76a758ae58aee81c5fd35fbf3a855b775\
+         4a36d4a0672df95d6c44a81cf7620c2d",
+        digest: "31a452d6164d904bb5724c878280231eae705c29ce9d4bc7d58e020e1085f17e\
+         ebcc1a38f0ed0bf2b344d81fbd896825",
+        r: "50835a9251bad008106177ef004b091a1e4235cd0da84fff54542b0ed755c1d6\
+         f251609d14ecf18f9e1ddfe69b946e32",
+        s: "0475f3d30c6463b646e8d3bf2455830314611cbde404be518b14464fdb195fdc\
+         c92eb222e61f426a4a592c00a6a89721",
+        valid: true,
+    },
+    SigVer {
+        case: "CAVP SigGen, valid",
+        qx: "5d42d6301c54a438f65970bae2a098cbc567e98840006e356221966c86d82e8e\
+         ca515bca850eaa3cd41f175f03a0cbfd",
+        qy: "4aef5a0ceece95d382bd70ab5ce1cb77408bae42b51a08816d5e5e1d3da8c18f\
+         cc95564a752730b0aabea983ccea4e2e",
+        digest: "a92784916a40feaebfeab16ea28c0c65e45c5e81eb634052944865708072e201\
+         10bd669a9838d7e722e94ac75245cdd3",
+        r: "fb318f4cb1276282bb43f733a7fb7c567ce94f4d02924fc758635ab2d1107108\
+         bf159b85db080cdc3b30fbb5400016f3",
+        s: "588e3d7af5da03eae255ecb1813100d95edc243476b724b22db8e85377660d76\
+         45ddc1c2c2ee4eaea8b683dbe22f86ca",
+        valid: true,
+    },
+    SigVer {
+        case: "message changed",
+        qx: "c2b47944fb5de342d03285880177ca5f7d0f2fcad7678cce4229d6e1932fcac1\
+         1bfc3c3e97d942a3c56bf34123013dbf",
+        qy: "37257906a8223866eda0743c519616a76a758ae58aee81c5fd35fbf3a855b775\
+         4a36d4a0672df95d6c44a81cf7620c2d",
+        digest: "31a452d6164d904bb5724c878280231eae705c29ce9d4bc7d58e020e1085f17e\
+         ebcc1a38f0ed0bf2b344d81fbd896824",
+        r: "50835a9251bad008106177ef004b091a1e4235cd0da84fff54542b0ed755c1d6\
+         f251609d14ecf18f9e1ddfe69b946e32",
+        s: "0475f3d30c6463b646e8d3bf2455830314611cbde404be518b14464fdb195fdc\
+         c92eb222e61f426a4a592c00a6a89721",
+        valid: false,
+    },
+    SigVer {
+        case: "R changed",
+        qx: "c2b47944fb5de342d03285880177ca5f7d0f2fcad7678cce4229d6e1932fcac1\
+         1bfc3c3e97d942a3c56bf34123013dbf",
+        qy: "37257906a8223866eda0743c519616a76a758ae58aee81c5fd35fbf3a855b775\
+         4a36d4a0672df95d6c44a81cf7620c2d",
+        digest: "31a452d6164d904bb5724c878280231eae705c29ce9d4bc7d58e020e1085f17e\
+         ebcc1a38f0ed0bf2b344d81fbd896825",
+        r: "50835a9251bad008106177ef004b091a1e4235cd0da84fff54542b0ed755c1d6\
+         f251609d14ecf18f9e1ddfe69b946f32",
+        s: "0475f3d30c6463b646e8d3bf2455830314611cbde404be518b14464fdb195fdc\
+         c92eb222e61f426a4a592c00a6a89721",
+        valid: false,
+    },
+    SigVer {
+        case: "S changed",
+        qx: "c2b47944fb5de342d03285880177ca5f7d0f2fcad7678cce4229d6e1932fcac1\
+         1bfc3c3e97d942a3c56bf34123013dbf",
+        qy: "37257906a8223866eda0743c519616a76a758ae58aee81c5fd35fbf3a855b775\
+         4a36d4a0672df95d6c44a81cf7620c2d",
+        digest: "31a452d6164d904bb5724c878280231eae705c29ce9d4bc7d58e020e1085f17e\
+         ebcc1a38f0ed0bf2b344d81fbd896825",
+        r: "50835a9251bad008106177ef004b091a1e4235cd0da84fff54542b0ed755c1d6\
+         f251609d14ecf18f9e1ddfe69b946e32",
+        s: "0475f3d30c6463b646e8d3bf2455830314611cbde404be518b14464fdb195fdc\
+         c92eb222e61f426a4a592c00a6a89621",
+        valid: false,
+    },
+    SigVer {
+        case: "Q changed",
+        qx: "5d42d6301c54a438f65970bae2a098cbc567e98840006e356221966c86d82e8e\
+         ca515bca850eaa3cd41f175f03a0cbfd",
+        qy: "4aef5a0ceece95d382bd70ab5ce1cb77408bae42b51a08816d5e5e1d3da8c18f\
+         cc95564a752730b0aabea983ccea4e2e",
+        digest: "31a452d6164d904bb5724c878280231eae705c29ce9d4bc7d58e020e1085f17e\
+         ebcc1a38f0ed0bf2b344d81fbd896825",
+        r: "50835a9251bad008106177ef004b091a1e4235cd0da84fff54542b0ed755c1d6\
+         f251609d14ecf18f9e1ddfe69b946e32",
+        s: "0475f3d30c6463b646e8d3bf2455830314611cbde404be518b14464fdb195fdc\
+         c92eb222e61f426a4a592c00a6a89721",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, empty message",
+        qx: "29bdb76d5fa741bfd70233cb3a66cc7d44beb3b0663d92a8136650478bcefb61\
+         ef182e155a54345a5e8e5e88f064e5bc",
+        qy: "9a525ab7f764dad3dae1468c2b419f3b62b9ba917d5e8c4fb1ec47404a3fc764\
+         74b2713081be9db4c00e043ada9fc4a3",
+        digest: "38b060a751ac96384cd9327eb1b1e36a21fdb71114be07434c0cc7bf63f6e1da\
+         274edebfe76f65fbd51ad2f14898b95b",
+        r: "32401249714e9091f05a5e109d5c1216fdc05e98614261aa0dbd9e9cd4415dee\
+         29238afbd3b103c1e40ee5c9144aee0f",
+        s: "4326756fb2c4fd726360dd6479b5849478c7a9d054a833a58c1631c33b63c344\
+         1336ddf2c7fe0ed129aae6d4ddfeb753",
+        valid: true,
+    },
+    SigVer {
+        case: "Wycheproof, small r and s",
+        qx: "554f2fd0b700a9f4568752b673d9c0d29dc96c10fe67e38c6d6d339bfafe05f9\
+         70da8c3d2164e82031307a44bd322511",
+        qy: "71312b61b59113ff0bd3b8a9a4934df262aa8096f840e9d8bffa5d7491ded87b\
+         38c496f9b9e4f0ba1089f8d3ffc88a9f",
+        digest: "f9b127f0d81ebcd17b7ba0ea131c660d340b05ce557c82160e0f793de07d3817\
+         9023942871acb7002dfafdfffc8deace",
+        r: "0000000000000000000000000000000000000000000000000000000000000000\
+         00000000000000000000000000000002",
+        s: "0000000000000000000000000000000000000000000000000000000000000000\
+         00000000000000000000000000000001",
+        valid: true,
+    },
+    SigVer {
+        case: "Wycheproof, small s",
+        qx: "bd3d91f003e18adbea73079d4eba23b91fc17fcec14c9eb15a193fbc9ca39c8c\
+         747cd7a2c9623e05dd587ccbb8ab4c44",
+        qy: "3adb0a0706aa5ea7a68042082fccefc979612a7a1a3d694b00793b03f89bff86\
+         6a8b97c8e77990c29360ce795036c764",
+        digest: "f9b127f0d81ebcd17b7ba0ea131c660d340b05ce557c82160e0f793de07d3817\
+         9023942871acb7002dfafdfffc8deace",
+        r: "55555555555555555555555555555555555555555555555542766f2b5167b9f5\
+         1d5e0490c2e58d28f9a40878eeec6326",
+        s: "0000000000000000000000000000000000000000000000000000000000000000\
+         00000000000000000000000000000001",
+        valid: true,
+    },
+    SigVer {
+        case: "Wycheproof, r = 0",
+        qx: "2da57dda1089276a543f9ffdac0bff0d976cad71eb7280e7d9bfd9fee4bdb2f2\
+         0f47ff888274389772d98cc5752138aa",
+        qy: "4b6d054d69dcf3e25ec49df870715e34883b1836197d76f8ad962e78f6571bbc\
+         7407b0d6091f9e4d88f014274406174f",
+        digest: "f9b127f0d81ebcd17b7ba0ea131c660d340b05ce557c82160e0f793de07d3817\
+         9023942871acb7002dfafdfffc8deace",
+        r: "0000000000000000000000000000000000000000000000000000000000000000\
+         00000000000000000000000000000000",
+        s: "e7bf25603e2d07076ff30b7a2abec473da8b11c572b35fc631991d5de62ddca7\
+         525aaba89325dfd04fecc47bff426f82",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, s = 0",
+        qx: "2da57dda1089276a543f9ffdac0bff0d976cad71eb7280e7d9bfd9fee4bdb2f2\
+         0f47ff888274389772d98cc5752138aa",
+        qy: "4b6d054d69dcf3e25ec49df870715e34883b1836197d76f8ad962e78f6571bbc\
+         7407b0d6091f9e4d88f014274406174f",
+        digest: "f9b127f0d81ebcd17b7ba0ea131c660d340b05ce557c82160e0f793de07d3817\
+         9023942871acb7002dfafdfffc8deace",
+        r: "12b30abef6b5476fe6b612ae557c0425661e26b44b1bfe19daf2ca28e3113083\
+         ba8e4ae4cc45a0320abd3394f1c548d7",
+        s: "0000000000000000000000000000000000000000000000000000000000000000\
+         00000000000000000000000000000000",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, r = s = 0",
+        qx: "2da57dda1089276a543f9ffdac0bff0d976cad71eb7280e7d9bfd9fee4bdb2f2\
+         0f47ff888274389772d98cc5752138aa",
+        qy: "4b6d054d69dcf3e25ec49df870715e34883b1836197d76f8ad962e78f6571bbc\
+         7407b0d6091f9e4d88f014274406174f",
+        digest: "f9b127f0d81ebcd17b7ba0ea131c660d340b05ce557c82160e0f793de07d3817\
+         9023942871acb7002dfafdfffc8deace",
+        r: "0000000000000000000000000000000000000000000000000000000000000000\
+         00000000000000000000000000000000",
+        s: "0000000000000000000000000000000000000000000000000000000000000000\
+         00000000000000000000000000000000",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, r = n",
+        qx: "2da57dda1089276a543f9ffdac0bff0d976cad71eb7280e7d9bfd9fee4bdb2f2\
+         0f47ff888274389772d98cc5752138aa",
+        qy: "4b6d054d69dcf3e25ec49df870715e34883b1836197d76f8ad962e78f6571bbc\
+         7407b0d6091f9e4d88f014274406174f",
+        digest: "f9b127f0d81ebcd17b7ba0ea131c660d340b05ce557c82160e0f793de07d3817\
+         9023942871acb7002dfafdfffc8deace",
+        r: "ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf\
+         581a0db248b0a77aecec196accc52973",
+        s: "0000000000000000000000000000000000000000000000000000000000000000\
+         00000000000000000000000000000000",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, s = n",
+        qx: "2da57dda1089276a543f9ffdac0bff0d976cad71eb7280e7d9bfd9fee4bdb2f2\
+         0f47ff888274389772d98cc5752138aa",
+        qy: "4b6d054d69dcf3e25ec49df870715e34883b1836197d76f8ad962e78f6571bbc\
+         7407b0d6091f9e4d88f014274406174f",
+        digest: "f9b127f0d81ebcd17b7ba0ea131c660d340b05ce557c82160e0f793de07d3817\
+         9023942871acb7002dfafdfffc8deace",
+        r: "0000000000000000000000000000000000000000000000000000000000000000\
+         00000000000000000000000000000000",
+        s: "ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf\
+         581a0db248b0a77aecec196accc52973",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, r > n",
+        qx: "2da57dda1089276a543f9ffdac0bff0d976cad71eb7280e7d9bfd9fee4bdb2f2\
+         0f47ff888274389772d98cc5752138aa",
+        qy: "4b6d054d69dcf3e25ec49df870715e34883b1836197d76f8ad962e78f6571bbc\
+         7407b0d6091f9e4d88f014274406174f",
+        digest: "f9b127f0d81ebcd17b7ba0ea131c660d340b05ce557c82160e0f793de07d3817\
+         9023942871acb7002dfafdfffc8deace",
+        r: "ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf\
+         581a0db248b0a77aecec196accc52974",
+        s: "0000000000000000000000000000000000000000000000000000000000000000\
+         00000000000000000000000000000000",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, s > n",
+        qx: "2da57dda1089276a543f9ffdac0bff0d976cad71eb7280e7d9bfd9fee4bdb2f2\
+         0f47ff888274389772d98cc5752138aa",
+        qy: "4b6d054d69dcf3e25ec49df870715e34883b1836197d76f8ad962e78f6571bbc\
+         7407b0d6091f9e4d88f014274406174f",
+        digest: "f9b127f0d81ebcd17b7ba0ea131c660d340b05ce557c82160e0f793de07d3817\
+         9023942871acb7002dfafdfffc8deace",
+        r: "0000000000000000000000000000000000000000000000000000000000000000\
+         00000000000000000000000000000000",
+        s: "ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf\
+         581a0db248b0a77aecec196accc52974",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, r and s >= n",
+        qx: "2da57dda1089276a543f9ffdac0bff0d976cad71eb7280e7d9bfd9fee4bdb2f2\
+         0f47ff888274389772d98cc5752138aa",
+        qy: "4b6d054d69dcf3e25ec49df870715e34883b1836197d76f8ad962e78f6571bbc\
+         7407b0d6091f9e4d88f014274406174f",
+        digest: "f9b127f0d81ebcd17b7ba0ea131c660d340b05ce557c82160e0f793de07d3817\
+         9023942871acb7002dfafdfffc8deace",
+        r: "ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf\
+         581a0db248b0a77aecec196accc52973",
+        s: "ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf\
+         581a0db248b0a77aecec196accc52973",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, r, s in range but wrong",
+        qx: "2da57dda1089276a543f9ffdac0bff0d976cad71eb7280e7d9bfd9fee4bdb2f2\
+         0f47ff888274389772d98cc5752138aa",
+        qy: "4b6d054d69dcf3e25ec49df870715e34883b1836197d76f8ad962e78f6571bbc\
+         7407b0d6091f9e4d88f014274406174f",
+        digest: "f9b127f0d81ebcd17b7ba0ea131c660d340b05ce557c82160e0f793de07d3817\
+         9023942871acb7002dfafdfffc8deace",
+        r: "10b30abef6b5476fe6b612ae557c0425661e26b44b1bfe19daf2ca28e3113083\
+         ba8e4ae4cc45a0320abd3394f1c548d7",
+        s: "e7bf25603e2d07076ff30b7a2abec473da8b11c572b35fc631991d5de62ddca7\
+         525aaba89325dfd04fecc47bff426f82",
+        valid: false,
+    },
+];
+
+const P384_SCALAR_MUL: [ScalarMul; 7] = [
+    ScalarMul {
+        k: "0000000000000000000000000000000000000000000000000000000000000000\
+         00000000000000000000000000000002",
+        x: "08d999057ba3d2d969260045c55b97f089025959a6f434d651d207d19fb96e9e\
+         4fe0e86ebe0e64f85b96a9c75295df61",
+        y: "8e80f1fa5b1b3cedb7bfe8dffd6dba74b275d875bc6cc43e904e505f256ab425\
+         5ffd43e94d39e22d61501e700a940e80",
+    },
+    ScalarMul {
+        k: "0000000000000000000000000000000000000000000000000000000000000000\
+         00000000000000000000000000000014",
+        x: "605508ec02c534bceee9484c86086d2139849e2b11c1a9ca1e2808dec2eaf161\
+         ac8a105d70d4f85c50599be5800a623f",
+        y: "5158ee87962ac6b81f00a103b8543a07381b7639a3a65f1353aef11b733106dd\
+         e92e99b78de367b48e238c38dad8eedd",
+    },
+    ScalarMul {
+        k: "0000000000000000000000000000000000000000000000000000000000000000\
+         0000000000000000018ebbb95eed0e13",
+        x: "a499efe48839bc3abcd1c5cedbdd51904f9514db44f4686db918983b0c9dc3ae\
+         e05a88b72433e9515f91a329f5f4fa60",
+        y: "3b7ca28ef31f809c2f1ba24aaed847d0f8b406a4b8968542de139db5828ca410\
+         e615d1182e25b91b1131e230b727d36a",
+    },
+    ScalarMul {
+        k: "0000000000000000000000000000000000000000000000000000000000000000\
+         00159d893d4cdd747246cdca43590e13",
+        x: "90a0b1cac601676b083f21e07bc7090a3390fe1b9c7f61d842d27fa315fb38d8\
+         3667a11a71438773e483f2a114836b24",
+        y: "3197d3c6123f0d6cd65d5f0de106fef36656cb16dc7cd1a6817eb1d51510135a\
+         8f492f72665cfd1053f75ed03a7d04c9",
+    },
+    ScalarMul {
+        k: "41ffc1fffffe01fffc0003fffe0007c001fff00003fff07ffe0007c000000003\
+         ffffff807fff8007fffff800fffe0000",
+        x: "f2a066bd332dc59bbc3d01da1b124c687d8bb44611186422de94c1da4ecf150e\
+         664d353ccdb5cb2652685f8eb4d2cd49",
+        y: "d6ed0bf75fdd8e53d87765fa746835b673881d6d1907163a2c43990d75b45429\
+         4f942ec571ad5aae1806caf2bb8e9a4a",
+    },
+    ScalarMul {
+        k: "7ff0001fff000fffffffff80007ffc0003f80001fff8000000001fc000000fff\
+         000000007ffc01fffc020000003fc000",
+        x: "6842cfe3589ac268818291f31d44177a9168dcbc19f321ed66d81ecf59e31b54\
+         cca0ddfd4c4136780171748d69a91c54",
+        y: "e3a5ecd5ac725f13dbc631f358c6e817edcf3a613b83832741a9db591a0bae76\
+         7fc714f70c2e7ea891e4312047deccc0",
+    },
+    ScalarMul {
+        k: "ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf\
+         581a0db248b0a77aecec196accc52972",
+        x: "aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a38\
+         5502f25dbf55296c3a545e3872760ab7",
+        y: "c9e821b569d9d390a26167406d6d23d6070be242d765eb831625ceec4a0f473e\
+         f59f4e30e2817e6285bce2846f15f1a0",
+    },
+];
+
+const P521_SIGVER: [SigVer; 18] = [
+    SigVer {
+        case: "CAVP SigGen, valid",
+        qx: "0061387fd6b95914e885f912edfbb5fb274655027f216c4091ca83e19336740f\
+         d81aedfe047f51b42bdf68161121013e0d55b117a14e4303f926c8debb77a7fd\
+         aad1",
+        qy: "00e7d0c75c38626e895ca21526b9f9fdf84dcecb93f2b233390550d2b1463b7e\
+         e3f58df7346435ff0434199583c97c665a97f12f706f2357da4b40288def888e\
+         59e6",
+        digest: "65f83408092261bda599389df03382c5be01a81fe00a36f3f4bb6541263f8016\
+         27c440e50809712b0cace7c217e6e5051af81de9bfec3204dcd63c4f9a741047",
+        r: "004de826ea704ad10bc0f7538af8a3843f284f55c8b946af9235af5af74f2b76\
+         e099e4bc72fd79d28a380f8d4b4c919ac290d248c37983ba05aea42e2dd79fdd\
+         33e8",
+        s: "0087488c859a96fea266ea13bf6d114c429b163be97a57559086edb64aed4a18\
+         594b46fb9efc7fd25d8b2de8f09ca0587f54bd287299f47b2ff124aac566e8ee\
+         3b43",
+        valid: true,
+    },
+    SigVer {
+        case: "CAVP SigGen, valid",
+        qx: "004d5c8afee038984d2ea96681ec0dccb6b52dfa4ee2e2a77a23c8cf43ef1990\
+         5a34d6f5d8c5cf0981ed804d89d175b17d1a63522ceb1e785c0f5a1d2f3d15e5\
+         1352",
+        qy: "0014368b8e746807b2b68f3615cd78d761a464ddd7918fc8df51d225962fdf1e\
+         3dc243e265100ff0ec133359e332e44dd49afd8e5f38fe86133573432d33c02f\
+         a0a3",
+        digest: "a6200971c6a289e2fcb80f78ec08a5079ea2675efd68bcab479552aa5bcb8edf\
+         3c993c79d7cebcc23c20e5af41723052b871134cc71d5c57206182a7068cc39b",
+        r: "01a3c4a6386c4fb614fba2cb9e74201e1aaa0001aa931a2a939c92e04b834453\
+         5a20f53c6e3c69c75c2e5d2fe3549ed27e6713cb0f4a9a94f6189eb33bff7d45\
+         3fce",
+        s: "016a997f81aa0bea2e1469c8c1dab7df02a8b2086ba482c43af04f2174831f2b\
+         1761658795adfbdd44190a9b06fe10e578987369f3a2eced147cff89d8c2818f\
+         7471",
+        valid: true,
+    },
+    SigVer {
+        case: "message changed",
+        qx: "0061387fd6b95914e885f912edfbb5fb274655027f216c4091ca83e19336740f\
+         d81aedfe047f51b42bdf68161121013e0d55b117a14e4303f926c8debb77a7fd\
+         aad1",
+        qy: "00e7d0c75c38626e895ca21526b9f9fdf84dcecb93f2b233390550d2b1463b7e\
+         e3f58df7346435ff0434199583c97c665a97f12f706f2357da4b40288def888e\
+         59e6",
+        digest: "65f83408092261bda599389df03382c5be01a81fe00a36f3f4bb6541263f8016\
+         27c440e50809712b0cace7c217e6e5051af81de9bfec3204dcd63c4f9a741046",
+        r: "004de826ea704ad10bc0f7538af8a3843f284f55c8b946af9235af5af74f2b76\
+         e099e4bc72fd79d28a380f8d4b4c919ac290d248c37983ba05aea42e2dd79fdd\
+         33e8",
+        s: "0087488c859a96fea266ea13bf6d114c429b163be97a57559086edb64aed4a18\
+         594b46fb9efc7fd25d8b2de8f09ca0587f54bd287299f47b2ff124aac566e8ee\
+         3b43",
+        valid: false,
+    },
+    SigVer {
+        case: "R changed",
+        qx: "0061387fd6b95914e885f912edfbb5fb274655027f216c4091ca83e19336740f\
+         d81aedfe047f51b42bdf68161121013e0d55b117a14e4303f926c8debb77a7fd\
+         aad1",
+        qy: "00e7d0c75c38626e895ca21526b9f9fdf84dcecb93f2b233390550d2b1463b7e\
+         e3f58df7346435ff0434199583c97c665a97f12f706f2357da4b40288def888e\
+         59e6",
+        digest: "65f83408092261bda599389df03382c5be01a81fe00a36f3f4bb6541263f8016\
+         27c440e50809712b0cace7c217e6e5051af81de9bfec3204dcd63c4f9a741047",
+        r: "004de826ea704ad10bc0f7538af8a3843f284f55c8b946af9235af5af74f2b76\
+         e099e4bc72fd79d28a380f8d4b4c919ac290d248c37983ba05aea42e2dd79fdd\
+         32e8",
+        s: "0087488c859a96fea266ea13bf6d114c429b163be97a57559086edb64aed4a18\
+         594b46fb9efc7fd25d8b2de8f09ca0587f54bd287299f47b2ff124aac566e8ee\
+         3b43",
+        valid: false,
+    },
+    SigVer {
+        case: "S changed",
+        qx: "0061387fd6b95914e885f912edfbb5fb274655027f216c4091ca83e19336740f\
+         d81aedfe047f51b42bdf68161121013e0d55b117a14e4303f926c8debb77a7fd\
+         aad1",
+        qy: "00e7d0c75c38626e895ca21526b9f9fdf84dcecb93f2b233390550d2b1463b7e\
+         e3f58df7346435ff0434199583c97c665a97f12f706f2357da4b40288def888e\
+         59e6",
+        digest: "65f83408092261bda599389df03382c5be01a81fe00a36f3f4bb6541263f8016\
+         27c440e50809712b0cace7c217e6e5051af81de9bfec3204dcd63c4f9a741047",
+        r: "004de826ea704ad10bc0f7538af8a3843f284f55c8b946af9235af5af74f2b76\
+         e099e4bc72fd79d28a380f8d4b4c919ac290d248c37983ba05aea42e2dd79fdd\
+         33e8",
+        s: "0087488c859a96fea266ea13bf6d114c429b163be97a57559086edb64aed4a18\
+         594b46fb9efc7fd25d8b2de8f09ca0587f54bd287299f47b2ff124aac566e8ee\
+         3a43",
+        valid: false,
+    },
+    SigVer {
+        case: "Q changed",
+        qx: "004d5c8afee038984d2ea96681ec0dccb6b52dfa4ee2e2a77a23c8cf43ef1990\
+         5a34d6f5d8c5cf0981ed804d89d175b17d1a63522ceb1e785c0f5a1d2f3d15e5\
+         1352",
+        qy: "0014368b8e746807b2b68f3615cd78d761a464ddd7918fc8df51d225962fdf1e\
+         3dc243e265100ff0ec133359e332e44dd49afd8e5f38fe86133573432d33c02f\
+         a0a3",
+        digest: "65f83408092261bda599389df03382c5be01a81fe00a36f3f4bb6541263f8016\
+         27c440e50809712b0cace7c217e6e5051af81de9bfec3204dcd63c4f9a741047",
+        r: "004de826ea704ad10bc0f7538af8a3843f284f55c8b946af9235af5af74f2b76\
+         e099e4bc72fd79d28a380f8d4b4c919ac290d248c37983ba05aea42e2dd79fdd\
+         33e8",
+        s: "0087488c859a96fea266ea13bf6d114c429b163be97a57559086edb64aed4a18\
+         594b46fb9efc7fd25d8b2de8f09ca0587f54bd287299f47b2ff124aac566e8ee\
+         3b43",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, empty message",
+        qx: "012a908bfc5b70e17bdfae74294994808bf2a42dab59af8b0523a026d640a2a3\
+         d6d344520b62177e2cfa339ca42fb0883ec425904fbda2833a3b5b0a9a008113\
+         65d8",
+        qy: "012333d532f8f8eb1a623c378a3694651192bbda833e3b8d7b8f90b2bfc9b045\
+         f8a55e1b6a5fe1512c400c4bc9c86fd7c699d642f5cee9bb827c8b0abc0da01c\
+         ef1e",
+        digest: "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce\
+         47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e",
+        r: "01625d6115092a8e2ee21b9f8a425aa73814dec8b2335e86150ab4229f5a3421\
+         d2e6256d632c7a4365a1ee01dd2a936921bbb4551a512d1d4b5a56c314e4a025\
+         34c5",
+        s: "01b792d23f2649862595451055777bda1b02dc6cc8fef23231e44b921b16155c\
+         d42257441d75a790371e91819f0a9b1fd0ebd02c90b5b774527746ed9bfe743d\
+         be2f",
+        valid: true,
+    },
+    SigVer {
+        case: "Wycheproof, small r and s",
+        qx: "005e7eb6c4f481830abaad8a60ddb09891164ee418ea4cd2995062e227d33c22\
+         9fb737bf330703097d6b3b69a3f09e79c9de0b402bf846dd26b5bb1191cff801\
+         355d",
+        qy: "01789c9afda567e61de414437b0e93a17611e6e76853762bc0aff1e2bc9e46ce\
+         1285b931651d7129b85aef2c1fab1728e7eb4449b2956dec33e6cd7c9ba125c5\
+         cd9d",
+        digest: "43f800fbeaf9238c58af795bcdad04bc49cd850c394d3382953356b023210281\
+         757b30e19218a37cbd612086fbc158caa8b4e1acb2ec00837e5d941f342fb3cc",
+        r: "0000000000000000000000000000000000000000000000000000000000000000\
+         0000000000000000000000000000000000000000000000000000000000000000\
+         0001",
+        s: "0000000000000000000000000000000000000000000000000000000000000000\
+         0000000000000000000000000000000000000000000000000000000000000000\
+         0001",
+        valid: true,
+    },
+    SigVer {
+        case: "Wycheproof, small s",
+        qx: "01aa9f3a894b727d7a01b09c4f051b469d661de1e06915b599e211463319ac1b\
+         7ca8a6097f1be401d70a71d0b53655cdf9bef748d886e08ee7de2fa781e93ec4\
+         1a26",
+        qy: "01ba9ea67385e19894fc9cd4b0173ab215f7b96f23bc420665d46c75447bf200\
+         ae3ac7b42bd9b857fd1c85cce8ea9c8d2345e4687dd70df59f5149510735bb9c\
+         7b64",
+        digest: "43f800fbeaf9238c58af795bcdad04bc49cd850c394d3382953356b023210281\
+         757b30e19218a37cbd612086fbc158caa8b4e1acb2ec00837e5d941f342fb3cc",
+        r: "00aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\
+         aaa8c5d782813fba87792a9955c2fd033745693c9892d8896d3a3e7a925f85bd\
+         76ad",
+        s: "0000000000000000000000000000000000000000000000000000000000000000\
+         0000000000000000000000000000000000000000000000000000000000000000\
+         0001",
+        valid: true,
+    },
+    SigVer {
+        case: "Wycheproof, r = 0",
+        qx: "005c6457ec088d532f482093965ae53ccd07e556ed59e2af945cd8c7a95c1c64\
+         4f8a56a8a8a3cd77392ddd861e8a924dac99c69069093bd52a52fa6c56004a07\
+         4508",
+        qy: "007878d6d42e4b4dd1e9c0696cb3e19f63033c3db4e60d473259b3ebe079aaf0\
+         a986ee6177f8217a78c68b813f7e149a4e56fd9562c07fed3d895942d7d101cb\
+         83f6",
+        digest: "43f800fbeaf9238c58af795bcdad04bc49cd850c394d3382953356b023210281\
+         757b30e19218a37cbd612086fbc158caa8b4e1acb2ec00837e5d941f342fb3cc",
+        r: "0000000000000000000000000000000000000000000000000000000000000000\
+         0000000000000000000000000000000000000000000000000000000000000000\
+         0000",
+        s: "0028b5d0926a4172b349b0fd2e929487a5edb94b142df923a697e7446acdacdb\
+         a0a029e43d69111174dba2fe747122709a69ce69d5285e174a01a93022fea831\
+         8ac1",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, s = 0",
+        qx: "005c6457ec088d532f482093965ae53ccd07e556ed59e2af945cd8c7a95c1c64\
+         4f8a56a8a8a3cd77392ddd861e8a924dac99c69069093bd52a52fa6c56004a07\
+         4508",
+        qy: "007878d6d42e4b4dd1e9c0696cb3e19f63033c3db4e60d473259b3ebe079aaf0\
+         a986ee6177f8217a78c68b813f7e149a4e56fd9562c07fed3d895942d7d101cb\
+         83f6",
+        digest: "43f800fbeaf9238c58af795bcdad04bc49cd850c394d3382953356b023210281\
+         757b30e19218a37cbd612086fbc158caa8b4e1acb2ec00837e5d941f342fb3cc",
+        r: "004e4223ee43e8cb89de3b1339ffc279e582f82c7ab0f71bbde43dbe374ac75f\
+         fbef29acdf8e70750b9a04f66fda48351de7bbfd515720b0ec5cd736f9b73bdf\
+         8645",
+        s: "0000000000000000000000000000000000000000000000000000000000000000\
+         0000000000000000000000000000000000000000000000000000000000000000\
+         0000",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, r = s = 0",
+        qx: "005c6457ec088d532f482093965ae53ccd07e556ed59e2af945cd8c7a95c1c64\
+         4f8a56a8a8a3cd77392ddd861e8a924dac99c69069093bd52a52fa6c56004a07\
+         4508",
+        qy: "007878d6d42e4b4dd1e9c0696cb3e19f63033c3db4e60d473259b3ebe079aaf0\
+         a986ee6177f8217a78c68b813f7e149a4e56fd9562c07fed3d895942d7d101cb\
+         83f6",
+        digest: "43f800fbeaf9238c58af795bcdad04bc49cd850c394d3382953356b023210281\
+         757b30e19218a37cbd612086fbc158caa8b4e1acb2ec00837e5d941f342fb3cc",
+        r: "0000000000000000000000000000000000000000000000000000000000000000\
+         0000000000000000000000000000000000000000000000000000000000000000\
+         0000",
+        s: "0000000000000000000000000000000000000000000000000000000000000000\
+         0000000000000000000000000000000000000000000000000000000000000000\
+         0000",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, r = n",
+        qx: "005c6457ec088d532f482093965ae53ccd07e556ed59e2af945cd8c7a95c1c64\
+         4f8a56a8a8a3cd77392ddd861e8a924dac99c69069093bd52a52fa6c56004a07\
+         4508",
+        qy: "007878d6d42e4b4dd1e9c0696cb3e19f63033c3db4e60d473259b3ebe079aaf0\
+         a986ee6177f8217a78c68b813f7e149a4e56fd9562c07fed3d895942d7d101cb\
+         83f6",
+        digest: "43f800fbeaf9238c58af795bcdad04bc49cd850c394d3382953356b023210281\
+         757b30e19218a37cbd612086fbc158caa8b4e1acb2ec00837e5d941f342fb3cc",
+        r: "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+         fffa51868783bf2f966b7fcc0148f709a5d03bb5c9b8899c47aebb6fb71e9138\
+         6409",
+        s: "0000000000000000000000000000000000000000000000000000000000000000\
+         0000000000000000000000000000000000000000000000000000000000000000\
+         0000",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, s = n",
+        qx: "005c6457ec088d532f482093965ae53ccd07e556ed59e2af945cd8c7a95c1c64\
+         4f8a56a8a8a3cd77392ddd861e8a924dac99c69069093bd52a52fa6c56004a07\
+         4508",
+        qy: "007878d6d42e4b4dd1e9c0696cb3e19f63033c3db4e60d473259b3ebe079aaf0\
+         a986ee6177f8217a78c68b813f7e149a4e56fd9562c07fed3d895942d7d101cb\
+         83f6",
+        digest: "43f800fbeaf9238c58af795bcdad04bc49cd850c394d3382953356b023210281\
+         757b30e19218a37cbd612086fbc158caa8b4e1acb2ec00837e5d941f342fb3cc",
+        r: "0000000000000000000000000000000000000000000000000000000000000000\
+         0000000000000000000000000000000000000000000000000000000000000000\
+         0000",
+        s: "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+         fffa51868783bf2f966b7fcc0148f709a5d03bb5c9b8899c47aebb6fb71e9138\
+         6409",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, r > n",
+        qx: "005c6457ec088d532f482093965ae53ccd07e556ed59e2af945cd8c7a95c1c64\
+         4f8a56a8a8a3cd77392ddd861e8a924dac99c69069093bd52a52fa6c56004a07\
+         4508",
+        qy: "007878d6d42e4b4dd1e9c0696cb3e19f63033c3db4e60d473259b3ebe079aaf0\
+         a986ee6177f8217a78c68b813f7e149a4e56fd9562c07fed3d895942d7d101cb\
+         83f6",
+        digest: "43f800fbeaf9238c58af795bcdad04bc49cd850c394d3382953356b023210281\
+         757b30e19218a37cbd612086fbc158caa8b4e1acb2ec00837e5d941f342fb3cc",
+        r: "024e4223ee43e8cb89de3b1339ffc279e582f82c7ab0f71bbde43dbe374ac75f\
+         fbe97b3367122fa4a20584c271233f3ec3b7f7b31b0faa4d340b92a6b0d5cd17\
+         ea4e",
+        s: "0028b5d0926a4172b349b0fd2e929487a5edb94b142df923a697e7446acdacdb\
+         a0a029e43d69111174dba2fe747122709a69ce69d5285e174a01a93022fea831\
+         8ac1",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, s > n",
+        qx: "005c6457ec088d532f482093965ae53ccd07e556ed59e2af945cd8c7a95c1c64\
+         4f8a56a8a8a3cd77392ddd861e8a924dac99c69069093bd52a52fa6c56004a07\
+         4508",
+        qy: "007878d6d42e4b4dd1e9c0696cb3e19f63033c3db4e60d473259b3ebe079aaf0\
+         a986ee6177f8217a78c68b813f7e149a4e56fd9562c07fed3d895942d7d101cb\
+         83f6",
+        digest: "43f800fbeaf9238c58af795bcdad04bc49cd850c394d3382953356b023210281\
+         757b30e19218a37cbd612086fbc158caa8b4e1acb2ec00837e5d941f342fb3cc",
+        r: "004e4223ee43e8cb89de3b1339ffc279e582f82c7ab0f71bbde43dbe374ac75f\
+         fbef29acdf8e70750b9a04f66fda48351de7bbfd515720b0ec5cd736f9b73bdf\
+         8645",
+        s: "0228b5d0926a4172b349b0fd2e929487a5edb94b142df923a697e7446acdacdb\
+         a09a7b6ac4ecd0410b4722ca75ba197a403a0a1f9ee0e7b391b0649fda1d3969\
+         eeca",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, r and s >= n",
+        qx: "005c6457ec088d532f482093965ae53ccd07e556ed59e2af945cd8c7a95c1c64\
+         4f8a56a8a8a3cd77392ddd861e8a924dac99c69069093bd52a52fa6c56004a07\
+         4508",
+        qy: "007878d6d42e4b4dd1e9c0696cb3e19f63033c3db4e60d473259b3ebe079aaf0\
+         a986ee6177f8217a78c68b813f7e149a4e56fd9562c07fed3d895942d7d101cb\
+         83f6",
+        digest: "43f800fbeaf9238c58af795bcdad04bc49cd850c394d3382953356b023210281\
+         757b30e19218a37cbd612086fbc158caa8b4e1acb2ec00837e5d941f342fb3cc",
+        r: "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+         fffa51868783bf2f966b7fcc0148f709a5d03bb5c9b8899c47aebb6fb71e9138\
+         6409",
+        s: "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+         fffa51868783bf2f966b7fcc0148f709a5d03bb5c9b8899c47aebb6fb71e9138\
+         6409",
+        valid: false,
+    },
+    SigVer {
+        case: "Wycheproof, r, s in range but wrong",
+        qx: "005c6457ec088d532f482093965ae53ccd07e556ed59e2af945cd8c7a95c1c64\
+         4f8a56a8a8a3cd77392ddd861e8a924dac99c69069093bd52a52fa6c56004a07\
+         4508",
+        qy: "007878d6d42e4b4dd1e9c0696cb3e19f63033c3db4e60d473259b3ebe079aaf0\
+         a986ee6177f8217a78c68b813f7e149a4e56fd9562c07fed3d895942d7d101cb\
+         83f6",
+        digest: "43f800fbeaf9238c58af795bcdad04bc49cd850c394d3382953356b023210281\
+         757b30e19218a37cbd612086fbc158caa8b4e1acb2ec00837e5d941f342fb3cc",
+        r: "004c4223ee43e8cb89de3b1339ffc279e582f82c7ab0f71bbde43dbe374ac75f\
+         fbef29acdf8e70750b9a04f66fda48351de7bbfd515720b0ec5cd736f9b73bdf\
+         8645",
+        s: "0028b5d0926a4172b349b0fd2e929487a5edb94b142df923a697e7446acdacdb\
+         a0a029e43d69111174dba2fe747122709a69ce69d5285e174a01a93022fea831\
+         8ac1",
+        valid: false,
+    },
+];
+
+const P521_SCALAR_MUL: [ScalarMul; 7] = [
+    ScalarMul {
+        k: "0000000000000000000000000000000000000000000000000000000000000000\
+         0000000000000000000000000000000000000000000000000000000000000000\
+         0002",
+        x: "00433c219024277e7e682fcb288148c282747403279b1ccc06352c6e5505d769\
+         be97b3b204da6ef55507aa104a3a35c5af41cf2fa364d60fd967f43e3933ba6d\
+         783d",
+        y: "00f4bb8cc7f86db26700a7f3eceeeed3f0b5c6b5107c4da97740ab21a29906c4\
+         2dbbb3e377de9f251f6b93937fa99a3248f4eafcbe95edc0f4f71be356d661f4\
+         1b02",
+    },
+    ScalarMul {
+        k: "0000000000000000000000000000000000000000000000000000000000000000\
+         0000000000000000000000000000000000000000000000000000000000000000\
+         0014",
+        x: "018bdd7f1b889598a4653deeae39cc6f8cc2bd767c2ab0d93fb12e968fbed342\
+         b51709506339cb1049cb11dd48b9bdb3cd5cad792e43b74e16d8e2603bfb11b0\
+         344f",
+        y: "00c5aadbe63f68ca5b6b6908296959bf0af89ee7f52b410b9444546c550952d3\
+         11204da3bdddc6d4eae7edfaec1030da8ef837ccb22eee9cfc94dd3287fed099\
+         0f94",
+    },
+    ScalarMul {
+        k: "0000000000000000000000000000000000000000000000000000000000000000\
+         0000000000000000000000000000000000000000000000000000018ebbb95eed\
+         0e13",
+        x: "01650048fbd63e8c30b305bf36bd7643b91448ef2206e8a0ca84a140789a99b0\
+         423a0a2533ea079ca7e049843e69e5fa2c25a163819110cec1a30acbbb3a422a\
+         40d8",
+        y: "010c9c64a0e0db6052dbc5646687d06dece5e9e0703153efe9cb816fe025e853\
+         54d3c5f869d6db3f4c0c01b5f97919a5e72ceebe03042e5aa99112691cffc272\
+         4828",
+    },
+    ScalarMul {
+        k: "0000000000000000000000000000000000000000000000000000000000000000\
+         00000000000000000000000000000000000000159d893d4cdd747246cdca4359\
+         0e13",
+        x: "017e1370d39c9c63925daeeac571e21caaf60bd169191baee8352e0f54674443\
+         b29786243564abb705f6fc0fe5fc5d3f98086b67ca0be7ac8a9dec421d9f1bc6\
+         b37f",
+        y: "01cd559605ead19fbd99e83600a6a81a0489e6f20306ee0789ae00ce16a6efea\
+         2f42f7534186cf1c60df230bd9bcf8cb95e5028ad9820b2b1c0e15597ee54c46\
+         14a6",
+    },
+    ScalarMul {
+        k: "0083ff83fffffc03fff80007fffc000f8003ffe00007ffe0fffc000f80000000\
+         07ffffff00ffff000ffffff001fffc000000001c0000400000003803ffffffcf\
+         ffff",
+        x: "00b45cb84651c9d4f08858b867f82d816e84e94fe4cae3da5f65e420b08398d0\
+         c5bf019253a6c26d20671bdef0b8e6c1d348a4b0734687f73ac6a4cbb2e085c6\
+         8b3f",
+        y: "01c84942bbf538903062170a4ba8b3410d385719ba2037d29ca5248bfcbc8478\
+         220fec79244dcd45d31885a1764dee479ce20b12ceab62f9001c7aa4282ce4be\
+         7f56",
+    },
+    ScalarMul {
+        k: "000000000003fff7ffffffffffffffe007ffffffe3fffffffffc01ffe0001fe0\
+         1fffffff0000000000ffffffc0000000007ffffff03ff8000000000000c00000\
+         0000",
+        x: "0172cd22cbe0634b6bfee24bb1d350f384a945ed618ecad48aadc6c1bc0dcc10\
+         7f0ffe9fe14dc929f90153f390c25be5d3a73a56f9accb0c72c768753869732d\
+         0dc4",
+        y: "00d249cfb570da4cc48fb5426a928b43d7922f787373b6182408fbc71706e752\
+         7e8414c79167f3c999ff58de352d238f1fe7168c658d338f72696f2f889a97de\
+         23c5",
+    },
+    ScalarMul {
+        k: "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+         fffa51868783bf2f966b7fcc0148f709a5d03bb5c9b8899c47aebb6fb71e9138\
+         6408",
+        x: "00c6858e06b70404e9cd9e3ecb662395b4429c648139053fb521f828af606b4d\
+         3dbaa14b5e77efe75928fe1dc127a2ffa8de3348b3c1856a429bf97e7e31c2e5\
+         bd66",
+        y: "00e7c6d6958765c43ffba375a04bd382e426670abbb6a864bb97e85042e8d8c1\
+         99d368118d66a10bd9bf3aaf46fec052f89ecac38f795d8d3dbf77416b89602e\
+         99af",
+    },
+];
+
+#[entry]
+fn main() -> ! {
+    info!("Starting nRF54L15 P-384/P-521 example...");
+    let p = nrf54l15_app_pac::Peripherals::take().unwrap();
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
+
+    check(
+        &mut cracen,
+        "P-384",
+        (&P384_GX, &P384_GY),
+        &P384_D,
+        (&P384_QX, &P384_QY),
+        (&P384_R, &P384_S),
+    );
+    check(
+        &mut cracen,
+        "P-521",
+        (&P521_GX, &P521_GY),
+        &P521_D,
+        (&P521_QX, &P521_QY),
+        (&P521_R, &P521_S),
+    );
+
+    sigver::<48>(&mut cracen, "P-384", &P384_SIGVER);
+    sigver::<66>(&mut cracen, "P-521", &P521_SIGVER);
+    scalar_mul::<48>(&mut cracen, "P-384", &P384_SCALAR_MUL, (&P384_GX, &P384_GY));
+    scalar_mul::<66>(&mut cracen, "P-521", &P521_SCALAR_MUL, (&P521_GX, &P521_GY));
+
+    loop {
+        cortex_m::asm::nop();
+    }
+}
+
+fn check<const N: usize>(
+    cracen: &mut Cracen,
+    name: &str,
+    (gx, gy): (&[u8; N], &[u8; N]),
+    d: &[u8; N],
+    (qx, qy): (&[u8; N], &[u8; N]),
+    (r, s): (&[u8; N], &[u8; N]),
+) {
+    // The public key is d * G
+    let mut x = [0u8; N];
+    let mut y = [0u8; N];
+    cracen.ec_scalar_mul(d, gx, gy, &mut x, &mut y).unwrap();
+    assert_eq!((&x, &y), (qx, qy));
+    info!("{}: scalar multiplication OK", name);
+
+    cracen.ecdsa_verify(b"sample", r, s, qx, qy).unwrap();
+    let mut bad_s = *s;
+    bad_s[N - 1] ^= 1;
+    assert_eq!(
+        cracen.ecdsa_verify(b"sample", r, &bad_s, qx, qy),
+        Err(CracenError::InvalidSignature)
+    );
+    info!(
+        "{}: RFC 6979 signature verified, tampered one rejected",
+        name
+    );
+
+    // Signing uses a random nonce, so only the round trip can be checked
+    let (r, s) = cracen.ecdsa_sign(b"example", d).unwrap();
+    cracen.ecdsa_verify(b"example", &r, &s, qx, qy).unwrap();
+    info!("{}: sign/verify round trip OK", name);
+}
+
+fn unhex<const N: usize>(hex: &str) -> [u8; N] {
+    let mut out = [0u8; N];
+    hex::decode_to_slice(hex, &mut out).unwrap();
+    out
+}
+
+fn sigver<const N: usize>(cracen: &mut Cracen, name: &str, cases: &[SigVer]) {
+    for case in cases {
+        let mut digest = [0u8; 64];
+        let digest = &mut digest[..case.digest.len() / 2];
+        hex::decode_to_slice(case.digest, digest).unwrap();
+
+        let result = cracen.ecdsa_verify_prehash::<N>(
+            digest,
+            &unhex(case.r),
+            &unhex(case.s),
+            &unhex(case.qx),
+            &unhex(case.qy),
+        );
+        if case.valid {
+            assert_eq!(result, Ok(()), "{}: {}", name, case.case);
+        } else {
+            assert_eq!(
+                result,
+                Err(CracenError::InvalidSignature),
+                "{}: {}",
+                name,
+                case.case
+            );
+        }
+    }
+    info!("{}: {} SigVer cases OK", name, cases.len());
+}
+
+fn scalar_mul<const N: usize>(
+    cracen: &mut Cracen,
+    name: &str,
+    cases: &[ScalarMul],
+    (gx, gy): (&[u8; N], &[u8; N]),
+) {
+    for case in cases {
+        let mut x = [0u8; N];
+        let mut y = [0u8; N];
+        cracen
+            .ec_scalar_mul(&unhex(case.k), gx, gy, &mut x, &mut y)
+            .unwrap();
+        assert_eq!(
+            (x, y),
+            (unhex(case.x), unhex(case.y)),
+            "{}: k = {}",
+            name,
+            case.k
+        );
+    }
+    info!("{}: {} scalar multiplication KATs OK", name, cases.len());
+}
@@ -0,0 +1,153 @@
+#![no_std]
+#![no_main]
+
+use app_core::{Cracen, CracenError, HashAlg, HashState, NonceMode};
+use cortex_m_rt::entry;
+use defmt::info;
+use defmt_rtt as _;
+use panic_probe as _;
+
+// RFC 6979 A.2.5 and A.2.6: P-256 and P-384 keys signing "sample" with
+// hashes other than the curve's own
+const X256: [u8; 32] = [
+    0xc9, 0xaf, 0xa9, 0xd8, 0x45, 0xba, 0x75, 0x16, 0x6b, 0x5c, 0x21, 0x57, 0x67, 0xb1, 0xd6, 0x93,
+    0x4e, 0x50, 0xc3, 0xdb, 0x36, 0xe8, 0x9b, 0x12, 0x7b, 0x8a, 0x62, 0x2b, 0x12, 0x0f, 0x67, 0x21,
+];
+const UX256: [u8; 32] = [
+    0x60, 0xfe, 0xd4, 0xba, 0x25, 0x5a, 0x9d, 0x31, 0xc9, 0x61, 0xeb, 0x74, 0xc6, 0x35, 0x6d, 0x68,
+    0xc0, 0x49, 0xb8, 0x92, 0x3b, 0x61, 0xfa, 0x6c, 0xe6, 0x69, 0x62, 0x2e, 0x60, 0xf2, 0x9f, 0xb6,
+];
+const UY256: [u8; 32] = [
+    0x79, 0x03, 0xfe, 0x10, 0x08, 0xb8, 0xbc, 0x99, 0xa4, 0x1a, 0xe9, 0xe9, 0x56, 0x28, 0xbc, 0x64,
+    0xf2, 0xf1, 0xb2, 0x0c, 0x2d, 0x7e, 0x9f, 0x51, 0x77, 0xa3, 0xc2, 0x94, 0xd4, 0x46, 0x22, 0x99,
+];
+const P256_SHA256_R: [u8; 32] = [
+    0xef, 0xd4, 0x8b, 0x2a, 0xac, 0xb6, 0xa8, 0xfd, 0x11, 0x40, 0xdd, 0x9c, 0xd4, 0x5e, 0x81, 0xd6,
+    0x9d, 0x2c, 0x87, 0x7b, 0x56, 0xaa, 0xf9, 0x91, 0xc3, 0x4d, 0x0e, 0xa8, 0x4e, 0xaf, 0x37, 0x16,
+];
+const P256_SHA256_S: [u8; 32] = [
+    0xf7, 0xcb, 0x1c, 0x94, 0x2d, 0x65, 0x7c, 0x41, 0xd4, 0x36, 0xc7, 0xa1, 0xb6, 0xe2, 0x9f, 0x65,
+    0xf3, 0xe9, 0x00, 0xdb, 0xb9, 0xaf, 0xf4, 0x06, 0x4d, 0xc4, 0xab, 0x2f, 0x84, 0x3a, 0xcd, 0xa8,
+];
+const P256_SHA384_R: [u8; 32] = [
+    0x0e, 0xaf, 0xea, 0x03, 0x9b, 0x20, 0xe9, 0xb4, 0x23, 0x09, 0xfb, 0x1d, 0x89, 0xe2, 0x13, 0x05,
+    0x7c, 0xbf, 0x97, 0x3d, 0xc0, 0xcf, 0xc8, 0xf1, 0x29, 0xed, 0xdd, 0xc8, 0x00, 0xef, 0x77, 0x19,
+];
+const P256_SHA384_S: [u8; 32] = [
+    0x48, 0x61, 0xf0, 0x49, 0x1e, 0x69, 0x98, 0xb9, 0x45, 0x51, 0x93, 0xe3, 0x4e, 0x7b, 0x0d, 0x28,
+    0x4d, 0xdd, 0x71, 0x49, 0xa7, 0x4b, 0x95, 0xb9, 0x26, 0x1f, 0x13, 0xab, 0xde, 0x94, 0x09, 0x54,
+];
+const P256_SHA512_R: [u8; 32] = [
+    0x84, 0x96, 0xa6, 0x0b, 0x5e, 0x9b, 0x47, 0xc8, 0x25, 0x48, 0x88, 0x27, 0xe0, 0x49, 0x5b, 0x0e,
+    0x3f, 0xa1, 0x09, 0xec, 0x45, 0x68, 0xfd, 0x3f, 0x8d, 0x10, 0x97, 0x67, 0x8e, 0xb9, 0x7f, 0x00,
+];
+const P256_SHA512_S: [u8; 32] = [
+    0x23, 0x62, 0xab, 0x1a, 0xdb, 0xe2, 0xb8, 0xad, 0xf9, 0xcb, 0x9e, 0xda, 0xb7, 0x40, 0xea, 0x60,
+    0x49, 0xc0, 0x28, 0x11, 0x4f, 0x24, 0x60, 0xf9, 0x65, 0x54, 0xf6, 0x1f, 0xae, 0x33, 0x02, 0xfe,
+];
+const X384: [u8; 48] = [
+    0x6b, 0x9d, 0x3d, 0xad, 0x2e, 0x1b, 0x8c, 0x1c, 0x05, 0xb1, 0x98, 0x75, 0xb6, 0x65, 0x9f, 0x4d,
+    0xe2, 0x3c, 0x3b, 0x66, 0x7b, 0xf2, 0x97, 0xba, 0x9a, 0xa4, 0x77, 0x40, 0x78, 0x71, 0x37, 0xd8,
+    0x96, 0xd5, 0x72, 0x4e, 0x4c, 0x70, 0xa8, 0x25, 0xf8, 0x72, 0xc9, 0xea, 0x60, 0xd2, 0xed, 0xf5,
+];
+const UX384: [u8; 48] = [
+    0xec, 0x3a, 0x4e, 0x41, 0x5b, 0x4e, 0x19, 0xa4, 0x56, 0x86, 0x18, 0x02, 0x9f, 0x42, 0x7f, 0xa5,
+    0xda, 0x9a, 0x8b, 0xc4, 0xae, 0x92, 0xe0, 0x2e, 0x06, 0xaa, 0xe5, 0x28, 0x6b, 0x30, 0x0c, 0x64,
+    0xde, 0xf8, 0xf0, 0xea, 0x90, 0x55, 0x86, 0x60, 0x64, 0xa2, 0x54, 0x51, 0x54, 0x80, 0xbc, 0x13,
+];
+const UY384: [u8; 48] = [
+    0x80, 0x15, 0xd9, 0xb7, 0x2d, 0x7d, 0x57, 0x24, 0x4e, 0xa8, 0xef, 0x9a, 0xc0, 0xc6, 0x21, 0x89,
+    0x67, 0x08, 0xa5, 0x93, 0x67, 0xf9, 0xdf, 0xb9, 0xf5, 0x4c, 0xa8, 0x4b, 0x3f, 0x1c, 0x9d, 0xb1,
+    0x28, 0x8b, 0x23, 0x1c, 0x3a, 0xe0, 0xd4, 0xfe, 0x73, 0x44, 0xfd, 0x25, 0x33, 0x26, 0x47, 0x20,
+];
+const P384_SHA256_R: [u8; 48] = [
+    0x21, 0xb1, 0x3d, 0x1e, 0x01, 0x3c, 0x7f, 0xa1, 0x39, 0x2d, 0x03, 0xc5, 0xf9, 0x9a, 0xf8, 0xb3,
+    0x0c, 0x57, 0x0c, 0x6f, 0x98, 0xd4, 0xea, 0x8e, 0x35, 0x4b, 0x63, 0xa2, 0x1d, 0x3d, 0xaa, 0x33,
+    0xbd, 0xe1, 0xe8, 0x88, 0xe6, 0x33, 0x55, 0xd9, 0x2f, 0xa2, 0xb3, 0xc3, 0x6d, 0x8f, 0xb2, 0xcd,
+];
+const P384_SHA256_S: [u8; 48] = [
+    0xf3, 0xaa, 0x44, 0x3f, 0xb1, 0x07, 0x74, 0x5b, 0xf4, 0xbd, 0x77, 0xcb, 0x38, 0x91, 0x67, 0x46,
+    0x32, 0x06, 0x8a, 0x10, 0xca, 0x67, 0xe3, 0xd4, 0x5d, 0xb2, 0x26, 0x6f, 0xa7, 0xd1, 0xfe, 0xeb,
+    0xef, 0xdc, 0x63, 0xec, 0xcd, 0x1a, 0xc4, 0x2e, 0xc0, 0xcb, 0x86, 0x68, 0xa4, 0xfa, 0x0a, 0xb0,
+];
+const P384_SHA512_R: [u8; 48] = [
+    0xed, 0x09, 0x59, 0xd5, 0x88, 0x0a, 0xb2, 0xd8, 0x69, 0xae, 0x7f, 0x6c, 0x29, 0x15, 0xc6, 0xd6,
+    0x0f, 0x96, 0x50, 0x7f, 0x9c, 0xb3, 0xe0, 0x47, 0xc0, 0x04, 0x68, 0x61, 0xda, 0x4a, 0x79, 0x9c,
+    0xfe, 0x30, 0xf3, 0x5c, 0xc9, 0x00, 0x05, 0x6d, 0x7c, 0x99, 0xcd, 0x78, 0x82, 0x43, 0x37, 0x09,
+];
+const P384_SHA512_S: [u8; 48] = [
+    0x51, 0x2c, 0x8c, 0xce, 0xee, 0x38, 0x90, 0xa8, 0x40, 0x58, 0xce, 0x1e, 0x22, 0xdb, 0xc2, 0x19,
+    0x8f, 0x42, 0x32, 0x3c, 0xe8, 0xac, 0xa9, 0x13, 0x53, 0x29, 0xf0, 0x3c, 0x06, 0x8e, 0x51, 0x12,
+    0xdc, 0x7c, 0xc3, 0xef, 0x34, 0x46, 0xde, 0xfc, 0xeb, 0x01, 0xa4, 0x5c, 0x26, 0x67, 0xfd, 0xd5,
+];
+
+#[entry]
+fn main() -> ! {
+    info!("Starting nRF54L15 ECDSA prehash example...");
+    let p = nrf54l15_app_pac::Peripherals::take().unwrap();
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
+
+    // Digests longer than the order are truncated to its leftmost bits
+    for (alg, r, s) in [
+        (HashAlg::Sha2_384, P256_SHA384_R, P256_SHA384_S),
+        (HashAlg::Sha2_512, P256_SHA512_R, P256_SHA512_S),
+    ] {
+        let sig = cracen
+            .ecdsa_sign_with(alg, b"sample", &X256, NonceMode::Deterministic)
+            .unwrap();
+        assert_eq!(sig, (r, s));
+        cracen
+            .ecdsa_verify_with(alg, b"sample", &r, &s, &UX256, &UY256)
+            .unwrap();
+    }
+    info!("P-256 with SHA-384 and SHA-512 OK");
+
+    // and shorter ones zero-extended
+    for (alg, r, s) in [
+        (HashAlg::Sha2_256, P384_SHA256_R, P384_SHA256_S),
+        (HashAlg::Sha2_512, P384_SHA512_R, P384_SHA512_S),
+    ] {
+        let sig = cracen
+            .ecdsa_sign_with(alg, b"sample", &X384, NonceMode::Deterministic)
+            .unwrap();
+        assert_eq!(sig, (r, s));
+        cracen
+            .ecdsa_verify_with(alg, b"sample", &r, &s, &UX384, &UY384)
+            .unwrap();
+    }
+    info!("P-384 with SHA-256 and SHA-512 OK");
+
+    // A digest hashed piecewise, as for an image that does not fit in RAM
+    let mut state = HashState::init(HashAlg::Sha2_256);
+    state.update(&mut cracen, b"sam").unwrap();
+    state.update(&mut cracen, b"ple").unwrap();
+    let mut digest = [0u8; 32];
+    state.finalize(&mut cracen, &mut digest).unwrap();
+
+    let (r, s) = cracen
+        .ecdsa_sign_prehash(&digest, &X256, NonceMode::Deterministic)
+        .unwrap();
+    assert_eq!((r, s), (P256_SHA256_R, P256_SHA256_S));
+    cracen
+        .ecdsa_verify_prehash(&digest, &r, &s, &UX256, &UY256)
+        .unwrap();
+    cracen
+        .ecdsa_verify(b"sample", &r, &s, &UX256, &UY256)
+        .unwrap();
+
+    let mut wrong = digest;
+    wrong[0] ^= 1;
+    assert!(matches!(
+        cracen.ecdsa_verify_prehash(&wrong, &r, &s, &UX256, &UY256),
+        Err(CracenError::InvalidSignature)
+    ));
+    assert!(matches!(
+        cracen.ecdsa_sign_prehash(&digest[..31], &X256, NonceMode::Random),
+        Err(CracenError::InvalidInput)
+    ));
+    info!("Prehashed P-256 signature OK");
+
+    loop {
+        cortex_m::asm::nop();
+    }
+}
@@ -0,0 +1,77 @@
+#![no_std]
+#![no_main]
+
+use app_core::{Cracen, NonceMode};
+use cortex_m_rt::entry;
+use defmt::info;
+use defmt_rtt as _;
+use panic_probe as _;
+
+// RFC 6979 A.2.5: P-256 key pair and the SHA-256 signatures of "sample" and
+// "test"
+const X: [u8; 32] = [
+    0xc9, 0xaf, 0xa9, 0xd8, 0x45, 0xba, 0x75, 0x16, 0x6b, 0x5c, 0x21, 0x57, 0x67, 0xb1, 0xd6, 0x93,
+    0x4e, 0x50, 0xc3, 0xdb, 0x36, 0xe8, 0x9b, 0x12, 0x7b, 0x8a, 0x62, 0x2b, 0x12, 0x0f, 0x67, 0x21,
+];
+const UX: [u8; 32] = [
+    0x60, 0xfe, 0xd4, 0xba, 0x25, 0x5a, 0x9d, 0x31, 0xc9, 0x61, 0xeb, 0x74, 0xc6, 0x35, 0x6d, 0x68,
+    0xc0, 0x49, 0xb8, 0x92, 0x3b, 0x61, 0xfa, 0x6c, 0xe6, 0x69, 0x62, 0x2e, 0x60, 0xf2, 0x9f, 0xb6,
+];
+const UY: [u8; 32] = [
+    0x79, 0x03, 0xfe, 0x10, 0x08, 0xb8, 0xbc, 0x99, 0xa4, 0x1a, 0xe9, 0xe9, 0x56, 0x28, 0xbc, 0x64,
+    0xf2, 0xf1, 0xb2, 0x0c, 0x2d, 0x7e, 0x9f, 0x51, 0x77, 0xa3, 0xc2, 0x94, 0xd4, 0x46, 0x22, 0x99,
+];
+const SAMPLE_R: [u8; 32] = [
+    0xef, 0xd4, 0x8b, 0x2a, 0xac, 0xb6, 0xa8, 0xfd, 0x11, 0x40, 0xdd, 0x9c, 0xd4, 0x5e, 0x81, 0xd6,
+    0x9d, 0x2c, 0x87, 0x7b, 0x56, 0xaa, 0xf9, 0x91, 0xc3, 0x4d, 0x0e, 0xa8, 0x4e, 0xaf, 0x37, 0x16,
+];
+const SAMPLE_S: [u8; 32] = [
+    0xf7, 0xcb, 0x1c, 0x94, 0x2d, 0x65, 0x7c, 0x41, 0xd4, 0x36, 0xc7, 0xa1, 0xb6, 0xe2, 0x9f, 0x65,
+    0xf3, 0xe9, 0x00, 0xdb, 0xb9, 0xaf, 0xf4, 0x06, 0x4d, 0xc4, 0xab, 0x2f, 0x84, 0x3a, 0xcd, 0xa8,
+];
+const TEST_R: [u8; 32] = [
+    0xf1, 0xab, 0xb0, 0x23, 0x51, 0x83, 0x51, 0xcd, 0x71, 0xd8, 0x81, 0x56, 0x7b, 0x1e, 0xa6, 0x63,
+    0xed, 0x3e, 0xfc, 0xf6, 0xc5, 0x13, 0x2b, 0x35, 0x4f, 0x28, 0xd3, 0xb0, 0xb7, 0xd3, 0x83, 0x67,
+];
+const TEST_S: [u8; 32] = [
+    0x01, 0x9f, 0x41, 0x13, 0x74, 0x2a, 0x2b, 0x14, 0xbd, 0x25, 0x92, 0x6b, 0x49, 0xc6, 0x49, 0x15,
+    0x5f, 0x26, 0x7e, 0x60, 0xd3, 0x81, 0x4b, 0x4c, 0x0c, 0xc8, 0x42, 0x50, 0xe4, 0x6f, 0x00, 0x83,
+];
+
+#[entry]
+fn main() -> ! {
+    info!("Starting nRF54L15 RFC 6979 ECDSA example...");
+    let p = nrf54l15_app_pac::Peripherals::take().unwrap();
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
+
+    for (msg, r, s) in [
+        (&b"sample"[..], SAMPLE_R, SAMPLE_S),
+        (&b"test"[..], TEST_R, TEST_S),
+    ] {
+        let sig = cracen
+            .ecdsa_sign_with_nonce(msg, &X, NonceMode::Deterministic)
+            .unwrap();
+        assert_eq!(sig, (r, s));
+        cracen.ecdsa_verify(msg, &sig.0, &sig.1, &UX, &UY).unwrap();
+    }
+    info!("RFC 6979 A.2.5 signatures OK");
+
+    // Hedged signatures differ from the deterministic one and from each
+    // other, and still verify
+    let first = cracen
+        .ecdsa_sign_with_nonce(b"sample", &X, NonceMode::Hedged)
+        .unwrap();
+    let second = cracen
+        .ecdsa_sign_with_nonce(b"sample", &X, NonceMode::Hedged)
+        .unwrap();
+    assert_ne!(first.0, SAMPLE_R);
+    assert_ne!(first.0, second.0);
+    for (r, s) in [first, second] {
+        cracen.ecdsa_verify(b"sample", &r, &s, &UX, &UY).unwrap();
+    }
+    info!("Hedged signatures OK");
+
+    loop {
+        cortex_m::asm::nop();
+    }
+}
@@ -0,0 +1,129 @@
+#![no_std]
+#![no_main]
+
+use app_core::{Cracen, CracenError};
+use cortex_m_rt::entry;
+use defmt::info;
+use defmt_rtt as _;
+use panic_probe as _;
+
+// RFC 8032 7.1, TEST 1 (empty message)
+const SECRET_1: [u8; 32] = [
+    0x9d, 0x61, 0xb1, 0x9d, 0xef, 0xfd, 0x5a, 0x60, 0xba, 0x84, 0x4a, 0xf4, 0x92, 0xec, 0x2c, 0xc4,
+    0x44, 0x49, 0xc5, 0x69, 0x7b, 0x32, 0x69, 0x19, 0x70, 0x3b, 0xac, 0x03, 0x1c, 0xae, 0x7f, 0x60,
+];
+const PUBLIC_1: [u8; 32] = [
+    0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9, 0x64, 0x07, 0x3a,
+    0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02, 0x1a, 0x68, 0xf7, 0x07, 0x51, 0x1a,
+];
+const SIG_1: [u8; 64] = [
+    0xe5, 0x56, 0x43, 0x00, 0xc3, 0x60, 0xac, 0x72, 0x90, 0x86, 0xe2, 0xcc, 0x80, 0x6e, 0x82, 0x8a,
+    0x84, 0x87, 0x7f, 0x1e, 0xb8, 0xe5, 0xd9, 0x74, 0xd8, 0x73, 0xe0, 0x65, 0x22, 0x49, 0x01, 0x55,
+    0x5f, 0xb8, 0x82, 0x15, 0x90, 0xa3, 0x3b, 0xac, 0xc6, 0x1e, 0x39, 0x70, 0x1c, 0xf9, 0xb4, 0x6b,
+    0xd2, 0x5b, 0xf5, 0xf0, 0x59, 0x5b, 0xbe, 0x24, 0x65, 0x51, 0x41, 0x43, 0x8e, 0x7a, 0x10, 0x0b,
+];
+
+// RFC 8032 7.1, TEST 2
+const SECRET_2: [u8; 32] = [
+    0x4c, 0xcd, 0x08, 0x9b, 0x28, 0xff, 0x96, 0xda, 0x9d, 0xb6, 0xc3, 0x46, 0xec, 0x11, 0x4e, 0x0f,
+    0x5b, 0x8a, 0x31, 0x9f, 0x35, 0xab, 0xa6, 0x24, 0xda, 0x8c, 0xf6, 0xed, 0x4f, 0xb8, 0xa6, 0xfb,
+];
+const PUBLIC_2: [u8; 32] = [
+    0x3d, 0x40, 0x17, 0xc3, 0xe8, 0x43, 0x89, 0x5a, 0x92, 0xb7, 0x0a, 0xa7, 0x4d, 0x1b, 0x7e, 0xbc,
+    0x9c, 0x98, 0x2c, 0xcf, 0x2e, 0xc4, 0x96, 0x8c, 0xc0, 0xcd, 0x55, 0xf1, 0x2a, 0xf4, 0x66, 0x0c,
+];
+const SIG_2: [u8; 64] = [
+    0x92, 0xa0, 0x09, 0xa9, 0xf0, 0xd4, 0xca, 0xb8, 0x72, 0x0e, 0x82, 0x0b, 0x5f, 0x64, 0x25, 0x40,
+    0xa2, 0xb2, 0x7b, 0x54, 0x16, 0x50, 0x3f, 0x8f, 0xb3, 0x76, 0x22, 0x23, 0xeb, 0xdb, 0x69, 0xda,
+    0x08, 0x5a, 0xc1, 0xe4, 0x3e, 0x15, 0x99, 0x6e, 0x45, 0x8f, 0x36, 0x13, 0xd0, 0xf1, 0x1d, 0x8c,
+    0x38, 0x7b, 0x2e, 0xae, 0xb4, 0x30, 0x2a, 0xee, 0xb0, 0x0d, 0x29, 0x16, 0x12, 0xbb, 0x0c, 0x00,
+];
+
+// RFC 8032 7.1, TEST 3
+const SECRET_3: [u8; 32] = [
+    0xc5, 0xaa, 0x8d, 0xf4, 0x3f, 0x9f, 0x83, 0x7b, 0xed, 0xb7, 0x44, 0x2f, 0x31, 0xdc, 0xb7, 0xb1,
+    0x66, 0xd3, 0x85, 0x35, 0x07, 0x6f, 0x09, 0x4b, 0x85, 0xce, 0x3a, 0x2e, 0x0b, 0x44, 0x58, 0xf7,
+];
+const PUBLIC_3: [u8; 32] = [
+    0xfc, 0x51, 0xcd, 0x8e, 0x62, 0x18, 0xa1, 0xa3, 0x8d, 0xa4, 0x7e, 0xd0, 0x02, 0x30, 0xf0, 0x58,
+    0x08, 0x16, 0xed, 0x13, 0xba, 0x33, 0x03, 0xac, 0x5d, 0xeb, 0x91, 0x15, 0x48, 0x90, 0x80, 0x25,
+];
+const SIG_3: [u8; 64] = [
+    0x62, 0x91, 0xd6, 0x57, 0xde, 0xec, 0x24, 0x02, 0x48, 0x27, 0xe6, 0x9c, 0x3a, 0xbe, 0x01, 0xa3,
+    0x0c, 0xe5, 0x48, 0xa2, 0x84, 0x74, 0x3a, 0x44, 0x5e, 0x36, 0x80, 0xd7, 0xdb, 0x5a, 0xc3, 0xac,
+    0x18, 0xff, 0x9b, 0x53, 0x8d, 0x16, 0xf2, 0x90, 0xae, 0x67, 0xf7, 0x60, 0x98, 0x4d, 0xc6, 0x59,
+    0x4a, 0x7c, 0x15, 0xe9, 0x71, 0x6e, 0xd2, 0x8d, 0xc0, 0x27, 0xbe, 0xce, 0xea, 0x1e, 0xc4, 0x0a,
+];
+
+// TEST 1 key over the 300 bytes 00 01 02 .. ff 00 .. 2b, signed on the host
+// with OpenSSL, so the hash runs over several blocks
+const SIG_LONG: [u8; 64] = [
+    0xb1, 0x7c, 0x42, 0x83, 0x2c, 0x88, 0xfa, 0xcb, 0x5f, 0xa3, 0x22, 0xb5, 0x32, 0x93, 0xdb, 0xef,
+    0x80, 0x9d, 0x7f, 0xb0, 0xb4, 0x54, 0x17, 0x5d, 0xf9, 0xd6, 0x5e, 0x9a, 0xdb, 0x96, 0x57, 0x90,
+    0x46, 0xc7, 0x5c, 0x05, 0xe5, 0x1f, 0x32, 0x76, 0x0c, 0xec, 0x24, 0x75, 0x5c, 0x66, 0xf3, 0x93,
+    0x43, 0x1b, 0xab, 0x29, 0x05, 0xa4, 0xf3, 0x87, 0x21, 0x69, 0x76, 0xce, 0xef, 0x57, 0x78, 0x0f,
+];
+
+// Secret key, public key, message, signature
+type Vector<'a> = (&'a [u8; 32], &'a [u8; 32], &'a [u8], &'a [u8; 64]);
+
+#[entry]
+fn main() -> ! {
+    info!("Starting nRF54L15 Ed25519 example...");
+    let p = nrf54l15_app_pac::Peripherals::take().unwrap();
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
+
+    let mut long_msg = [0u8; 300];
+    for (i, b) in long_msg.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+
+    let vectors: [Vector; 4] = [
+        (&SECRET_1, &PUBLIC_1, b"", &SIG_1),
+        (&SECRET_2, &PUBLIC_2, &[0x72], &SIG_2),
+        (&SECRET_3, &PUBLIC_3, &[0xaf, 0x82], &SIG_3),
+        (&SECRET_1, &PUBLIC_1, &long_msg, &SIG_LONG),
+    ];
+    for (i, (secret, public, msg, sig)) in vectors.into_iter().enumerate() {
+        assert_eq!(&cracen.ed25519_public_key(secret).unwrap(), public);
+        assert_eq!(&cracen.ed25519_sign(secret, msg).unwrap(), sig);
+        cracen.ed25519_verify(public, msg, sig).unwrap();
+
+        let mut bad = *sig;
+        bad[0] ^= 1;
+        assert_eq!(
+            cracen.ed25519_verify(public, msg, &bad),
+            Err(CracenError::InvalidSignature)
+        );
+        info!("Vector {} OK", i + 1);
+    }
+
+    // S + L verifies in the group but must be rejected as non-canonical
+    let mut malleable = SIG_1;
+    let mut carry = 0u16;
+    for (s, l) in malleable[32..].iter_mut().zip(L) {
+        let sum = u16::from(*s) + u16::from(l) + carry;
+        *s = sum as u8;
+        carry = sum >> 8;
+    }
+    assert_eq!(
+        cracen.ed25519_verify(&PUBLIC_1, b"", &malleable),
+        Err(CracenError::InvalidSignature)
+    );
+    info!("Non-canonical S rejected");
+
+    let (secret, public) = cracen.ed25519_keygen().unwrap();
+    let sig = cracen.ed25519_sign(secret.as_bytes(), b"example").unwrap();
+    cracen.ed25519_verify(&public, b"example", &sig).unwrap();
+    info!("Keygen round trip OK");
+
+    loop {
+        cortex_m::asm::nop();
+    }
+}
+
+// Group order, little-endian
+const L: [u8; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+];
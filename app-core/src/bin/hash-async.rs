@@ -0,0 +1,65 @@
+#![no_std]
+#![no_main]
+
+use app_core::{Cracen, HashAlg, HashState};
+use defmt::info;
+use embassy_executor::Spawner;
+use embassy_nrf::bind_interrupts;
+use embassy_time::Timer;
+use {defmt_rtt as _, panic_probe as _};
+
+bind_interrupts!(struct Irqs {
+    CRACEN => app_core::InterruptHandler;
+});
+
+static BIG: [u8; 16 * 1024] = [b'a'; 16 * 1024];
+
+#[embassy_executor::task]
+async fn ticker() {
+    loop {
+        info!("tick");
+        Timer::after_millis(1).await;
+    }
+}
+
+#[embassy_executor::main]
+async fn main(spawner: Spawner) {
+    info!("Starting nRF54L15 CryptoMaster async hash example...");
+    let _ = embassy_nrf::init(Default::default());
+    let p = nrf54l15_app_pac::Peripherals::take().unwrap();
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
+    app_core::cracen_enable_interrupt(Irqs);
+
+    // Keeps logging while the engine works on the large inputs below
+    spawner.spawn(ticker()).unwrap();
+
+    let mut expected = [0u8; 32];
+    cracen.sha256(&BIG, &mut expected).unwrap();
+
+    let mut out = [0u8; 32];
+    cracen
+        .hash_async(&BIG, &mut out, HashAlg::Sha2_256)
+        .await
+        .unwrap();
+    info!("async SHA2_256: {:02x}", out);
+    defmt::assert_eq!(out, expected);
+
+    // Same message through the incremental API, blocking and async
+    let mut blocking = HashState::init(HashAlg::Sha2_512);
+    let mut state = HashState::init(HashAlg::Sha2_512);
+    for chunk in BIG.chunks(1000) {
+        blocking.update(&mut cracen, chunk).unwrap();
+        state.update_async(&mut cracen, chunk).await.unwrap();
+    }
+    let mut expected = [0u8; 64];
+    blocking.finalize(&mut cracen, &mut expected).unwrap();
+    let mut out = [0u8; 64];
+    state.finalize_async(&mut cracen, &mut out).await.unwrap();
+    defmt::assert_eq!(out, expected);
+
+    info!("async hashing matches the blocking API");
+
+    loop {
+        Timer::after_secs(1).await;
+    }
+}
@@ -1,44 +1,185 @@
 #![no_std]
 #![no_main]
 
+use app_core::{Cracen, HashAlg, HashState};
 use cortex_m_rt::entry;
 use defmt::info;
 use defmt_rtt as _;
 use panic_probe as _;
 
+// Split points around the 64- and 128-byte block boundaries
+const SPLITS: [usize; 10] = [0, 1, 2, 63, 64, 65, 127, 128, 129, 200];
+
+// FIPS 180-2 examples: (algorithm, block length, "abc", 1,000,000 x 'a')
+const KAT: [(HashAlg, usize, &str, &str); 5] = [
+    (
+        HashAlg::Sha1,
+        64,
+        "a9993e364706816aba3e25717850c26c9cd0d89d",
+        "34aa973cd4c4daa4f61eeb2bdbad27316534016f",
+    ),
+    (
+        HashAlg::Sha2_224,
+        64,
+        "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7",
+        "20794655980c91d8bbb4c1ea97618a4bf03f42581948b2ee4ee7ad67",
+    ),
+    (
+        HashAlg::Sha2_256,
+        64,
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0",
+    ),
+    (
+        HashAlg::Sha2_384,
+        128,
+        "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed\
+         8086072ba1e7cc2358baeca134c825a7",
+        "9d0e1809716474cb086e834e310a4a1ced149e9c00f248527972cec5704c2a5b\
+         07b8b3dc38ecc4ebae97ddd87f3d8985",
+    ),
+    (
+        HashAlg::Sha2_512,
+        128,
+        "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+         2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+        "e718483d0ce769644e2e42c7bc15b4638e1f98b13b2044285632a803afa973eb\
+         de0ff244877ea60a4cb0432ce577c31beb009c5c2c49aa2e4eadb217ad8cc09b",
+    ),
+];
+
+const MILLION: usize = 1_000_000;
+
 #[entry]
 fn main() -> ! {
     info!("Starting nRF54L15 CryptoMaster SHA example...");
-    // let p = nrf54l15_app_pac::Peripherals::take().unwrap();
     let p = nrf54l15_app_pac::Peripherals::take().unwrap();
-    let fst_upd: [u8; 2] = [98; 2];
-    let snd_upd: [u8; 130] = [98; 130];
-
-    let mut state = app_core::HashState::init(app_core::HashAlg::Sha2_256);
-    info!("initial_state: {}", state);
-    state.update(&fst_upd);
-    // info!("state after fst update: {}", state);
-    // state.update(&snd_upd);
-    // info!("state after snd update: {}", state);
-    let out = state.finalize(&p);
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
+    let msg: [u8; 300] = [98; 300];
 
-    info!("out: {:02x}", out);
+    compare::<20>(&mut cracen, HashAlg::Sha1, &msg);
+    compare::<28>(&mut cracen, HashAlg::Sha2_224, &msg);
+    compare::<32>(&mut cracen, HashAlg::Sha2_256, &msg);
+    compare::<48>(&mut cracen, HashAlg::Sha2_384, &msg);
+    compare::<64>(&mut cracen, HashAlg::Sha2_512, &msg);
 
-    let mut state = app_core::HashState::init(app_core::HashAlg::Sha2_256);
-    info!("initial_state: {}", state);
-    state.update(&fst_upd);
-    // info!("state after fst update: {}", state);
-    state.update(&snd_upd);
-    // info!("state after snd update: {}", state);
-    let out = state.finalize(&p);
-
-    info!("out: {:02x}", out);
+    for (alg, block_len, abc, million_a) in KAT {
+        match alg {
+            HashAlg::Sha1 => known_answer::<20>(&mut cracen, alg, block_len, abc, million_a),
+            HashAlg::Sha2_224 => known_answer::<28>(&mut cracen, alg, block_len, abc, million_a),
+            HashAlg::Sha2_256 => known_answer::<32>(&mut cracen, alg, block_len, abc, million_a),
+            HashAlg::Sha2_384 => known_answer::<48>(&mut cracen, alg, block_len, abc, million_a),
+            _ => known_answer::<64>(&mut cracen, alg, block_len, abc, million_a),
+        }
+    }
 
     info!("DONE");
 
-    // info!("out {:02x}", out.unwrap());
-
     loop {
         cortex_m::asm::nop();
     }
 }
+
+// Hashes `msg` in two and three pieces and checks every result against the
+// one-shot digest.
+fn compare<const N: usize>(cracen: &mut Cracen, alg: HashAlg, msg: &[u8]) {
+    let mut expected = [0u8; N];
+    cracen.hash(msg, &mut expected, alg).unwrap();
+    info!("{} one-shot: {:02x}", alg, expected);
+
+    for split in SPLITS {
+        let mut state = HashState::init(alg);
+        state.update(cracen, &msg[..split]).unwrap();
+        state.update(cracen, &msg[split..]).unwrap();
+        let mut out = [0u8; N];
+        state.finalize(cracen, &mut out).unwrap();
+        defmt::assert_eq!(out, expected, "{} split at {}", alg, split);
+
+        let mut state = HashState::init(alg);
+        state.update(cracen, &msg[..split / 2]).unwrap();
+        state.update(cracen, &msg[split / 2..split]).unwrap();
+        state.update(cracen, &msg[split..]).unwrap();
+        let mut out = [0u8; N];
+        state.finalize(cracen, &mut out).unwrap();
+        defmt::assert_eq!(out, expected, "{} split at {}/{}", alg, split / 2, split);
+    }
+
+    // Short messages never leave the first block
+    let mut short_expected = [0u8; N];
+    cracen.hash(&msg[..2], &mut short_expected, alg).unwrap();
+    let mut state = HashState::init(alg);
+    state.update(cracen, &msg[..2]).unwrap();
+    let mut out = [0u8; N];
+    state.finalize(cracen, &mut out).unwrap();
+    defmt::assert_eq!(out, short_expected, "{} short message", alg);
+
+    info!("{} incremental matches one-shot", alg);
+
+    // Export mid-stream, as if going to deep sleep, and resume from the blob
+    for split in SPLITS {
+        let mut state = HashState::init(alg);
+        state.update(cracen, &msg[..split]).unwrap();
+        let saved = state.export();
+        drop(state);
+
+        let mut state = HashState::import(&saved).unwrap();
+        state.update(cracen, &msg[split..]).unwrap();
+        let mut out = [0u8; N];
+        state.finalize(cracen, &mut out).unwrap();
+        defmt::assert_eq!(out, expected, "{} resumed at {}", alg, split);
+
+        // A flipped bit must be caught by the checksum
+        let mut corrupted = saved;
+        corrupted[100] ^= 0x01;
+        defmt::assert!(HashState::import(&corrupted).is_err());
+    }
+
+    info!("{} export/import resumes correctly", alg);
+}
+
+// Checks the incremental API against fixed digests rather than the one-shot
+// path, so a fault shared by both cannot hide. "abc" is split at every
+// position; the million 'a's are fed in pieces one byte short of, equal to
+// and one byte over the block length, so updates end just before, on and
+// just after block boundaries.
+fn known_answer<const N: usize>(
+    cracen: &mut Cracen,
+    alg: HashAlg,
+    block_len: usize,
+    abc_hex: &str,
+    million_a_hex: &str,
+) {
+    let mut expected = [0u8; N];
+    hex::decode_to_slice(abc_hex, &mut expected).unwrap();
+    for split in 0..=3 {
+        let mut state = HashState::init(alg);
+        state.update(cracen, &b"abc"[..split]).unwrap();
+        state.update(cracen, &b"abc"[split..]).unwrap();
+        let mut out = [0u8; N];
+        state.finalize(cracen, &mut out).unwrap();
+        defmt::assert_eq!(out, expected, "{} \"abc\" split at {}", alg, split);
+    }
+
+    hex::decode_to_slice(million_a_hex, &mut expected).unwrap();
+    let a = [b'a'; 129];
+    for chunk in [block_len - 1, block_len, block_len + 1] {
+        let mut state = HashState::init(alg);
+        let mut fed = 0;
+        while fed < MILLION {
+            let n = chunk.min(MILLION - fed);
+            state.update(cracen, &a[..n]).unwrap();
+            fed += n;
+        }
+        let mut out = [0u8; N];
+        state.finalize(cracen, &mut out).unwrap();
+        defmt::assert_eq!(
+            out,
+            expected,
+            "{} million 'a' in {}-byte pieces",
+            alg,
+            chunk
+        );
+    }
+
+    info!("{} known answers match", alg);
+}
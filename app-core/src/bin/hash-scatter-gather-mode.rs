@@ -1,6 +1,7 @@
 #![no_std]
 #![no_main]
 
+use app_core::{Cracen, HashAlg};
 use cortex_m_rt::entry;
 use defmt::info;
 use defmt_rtt as _;
@@ -10,30 +11,81 @@ use panic_probe as _;
 fn main() -> ! {
     info!("Starting nRF54L15 CryptoMaster SHA example...");
     let p = nrf54l15_app_pac::Peripherals::take().unwrap();
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
 
     let input = b"";
     info!("input: {:02x}", input);
 
     let mut out_sha1 = [0u8; 20];
-    app_core::cracen_sha1(&p, input, &mut out_sha1).unwrap();
+    cracen.sha1(input, &mut out_sha1).unwrap();
     info!("output bytes SHA1: {:02x}", out_sha1);
 
     let mut out_sha224 = [0u8; 28];
-    app_core::cracen_sha224(&p, input, &mut out_sha224).unwrap();
+    cracen.sha224(input, &mut out_sha224).unwrap();
     info!("output bytes SHA2_224: {:02x}", out_sha224);
 
     let mut out_sha256 = [0u8; 32];
-    app_core::cracen_sha256(&p, input, &mut out_sha256).unwrap();
+    cracen.sha256(input, &mut out_sha256).unwrap();
     info!("output bytes SHA2_256: {:02x}", out_sha256);
 
     let mut out_sha384 = [0u8; 48];
-    app_core::cracen_sha384(&p, input, &mut out_sha384).unwrap();
+    cracen.sha384(input, &mut out_sha384).unwrap();
     info!("output bytes SHA2_384: {:02x}", out_sha384);
 
     let mut out_sha512 = [0u8; 64];
-    app_core::cracen_sha512(&p, input, &mut out_sha512).unwrap();
+    cracen.sha512(input, &mut out_sha512).unwrap();
     info!("output bytes SHA2_512: {:02x}", out_sha512);
 
+    // Framed packet hashed in place: header, payload and trailer live in
+    // separate buffers with lengths that are not multiples of 4
+    let header = b"HDR";
+    let payload = b"the quick brown fox jumps over the lazy dog, twice over!";
+    let trailer = b"\x01\x02\x03\x04\x05";
+
+    let mut packet = [0u8; 64];
+    let len = header.len() + payload.len() + trailer.len();
+    packet[..3].copy_from_slice(header);
+    packet[3..3 + payload.len()].copy_from_slice(payload);
+    packet[3 + payload.len()..len].copy_from_slice(trailer);
+
+    let mut expected = [0u8; 32];
+    cracen.sha256(&packet[..len], &mut expected).unwrap();
+
+    let mut out = [0u8; 32];
+    cracen
+        .hash_vectored(
+            HashAlg::Sha2_256,
+            &[&header[..], &payload[..], &trailer[..]],
+            &mut out,
+        )
+        .unwrap();
+    info!("vectored SHA2_256: {:02x}", out);
+    defmt::assert_eq!(out, expected);
+
+    // Empty slices are skipped and one-byte fragments are realigned
+    let fragments: [&[u8]; 6] = [
+        &packet[..1],
+        &[],
+        &packet[1..2],
+        &packet[2..7],
+        &[],
+        &packet[7..len],
+    ];
+    let mut out = [0u8; 32];
+    cracen
+        .hash_vectored(HashAlg::Sha2_256, &fragments, &mut out)
+        .unwrap();
+    defmt::assert_eq!(out, expected);
+
+    let mut expected = [0u8; 64];
+    cracen.sha512(b"", &mut expected).unwrap();
+    let mut out = [0u8; 64];
+    cracen
+        .hash_vectored(HashAlg::Sha2_512, &[&[], &[]], &mut out)
+        .unwrap();
+    defmt::assert_eq!(out, expected);
+    info!("vectored hashing matches the contiguous input");
+
     loop {
         cortex_m::asm::nop();
     }
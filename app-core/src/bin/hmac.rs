@@ -1,24 +1,79 @@
 #![no_std]
 #![no_main]
 
+use app_core::{Cracen, HashAlg, Hmac};
 use cortex_m_rt::entry;
 use defmt::info;
 use defmt_rtt as _;
 use panic_probe as _;
 
+// (algorithm, RFC 2202/4231 test case 2, 131-byte 0xaa key over 2000 x 'a')
+const VECTORS: [(HashAlg, &str, &str); 5] = [
+    (
+        HashAlg::Sha1,
+        "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79",
+        "76bf17703c8864cb8e7e90f32d768df3bf547407",
+    ),
+    (
+        HashAlg::Sha2_224,
+        "a30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44",
+        "7a26cbd170c592460edead82ecacfda17631914335b2a49a5797f627",
+    ),
+    (
+        HashAlg::Sha2_256,
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        "93038f5c3567daaaa9e90509e67fda7c1d1fbcc07bffe0a57d3460f8a41026cd",
+    ),
+    (
+        HashAlg::Sha2_384,
+        "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e8e2240ca5e69e2c78b3239ecfab21649",
+        "7f493f04456ae58d51d25e0070a6ad3f15d8e698cac81e10613a6057271c06f872b21955dc0357ec6975c44d6d3244d5",
+    ),
+    (
+        HashAlg::Sha2_512,
+        "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+        "00c515e555dc33da5fbcf0ce1f3273efd48078b84cc7a7f71d08dc82288ce92e242c41d02122685bd362131c275a7411fb2c5e0574f67cb339c23c0f0eea3f5d",
+    ),
+];
+
 #[entry]
 fn main() -> ! {
     info!("Starting nRF54L15 CryptoMaster HMAC example...");
     let p = nrf54l15_app_pac::Peripherals::take().unwrap();
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
 
     let key = b"supersecretkey";
     let message = b"hello world";
 
     let mut tag = [0u8; 32];
-    app_core::cracen_hmac_sha256(&p, key, message, &mut tag).unwrap();
+    cracen.hmac_sha256(key, message, &mut tag).unwrap();
 
     info!("HMAC-SHA256: {:02x}", tag);
 
+    let long_key = [0xaa; 131];
+    let chunk = [b'a'; 100];
+
+    for (alg, short_hex, long_hex) in VECTORS {
+        let mut expected = [0u8; 64];
+        let len = short_hex.len() / 2;
+
+        hex::decode_to_slice(short_hex, &mut expected[..len]).unwrap();
+        let mut mac = Hmac::new(&mut cracen, alg, b"Jefe").unwrap();
+        mac.update(&mut cracen, b"what do ya ").unwrap();
+        mac.update(&mut cracen, b"want for nothing?").unwrap();
+        mac.verify(&mut cracen, &expected[..len]).unwrap();
+
+        // Key longer than a block and a message well past 1 KiB
+        hex::decode_to_slice(long_hex, &mut expected[..len]).unwrap();
+        let mut mac = Hmac::new(&mut cracen, alg, &long_key).unwrap();
+        for _ in 0..20 {
+            mac.update(&mut cracen, &chunk).unwrap();
+        }
+        mac.verify(&mut cracen, &expected[..len]).unwrap();
+
+        info!("HMAC-{}: OK", alg);
+    }
+
     loop {
         cortex_m::asm::nop();
     }
@@ -0,0 +1,69 @@
+#![no_std]
+#![no_main]
+
+use app_core::{APT_WINDOW, Cracen, HealthTestFailure, HealthTests, RCT_CUTOFF, STARTUP_SAMPLES};
+use cortex_m_rt::entry;
+use defmt::info;
+use defmt_rtt as _;
+use panic_probe as _;
+
+// Small LCG standing in for a healthy noise source
+fn noise(state: &mut u32) -> u8 {
+    *state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+    (*state >> 16) as u8
+}
+
+#[entry]
+fn main() -> ! {
+    info!("Starting nRF54L15 RNG health test example...");
+    let p = nrf54l15_app_pac::Peripherals::take().unwrap();
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
+
+    // Healthy samples pass and finish the startup test
+    let mut seed = 1;
+    let mut health = HealthTests::new();
+    for _ in 0..STARTUP_SAMPLES {
+        defmt::assert!(!health.startup_done());
+        health.feed(noise(&mut seed)).unwrap();
+    }
+    defmt::assert!(health.startup_done());
+
+    // Stuck-at output trips the repetition count test on the cutoff-th sample
+    let mut health = HealthTests::new();
+    for _ in 1..RCT_CUTOFF {
+        health.feed(0xaa).unwrap();
+    }
+    defmt::assert_eq!(health.feed(0xaa), Err(HealthTestFailure::RepetitionCount));
+    // and stays failed
+    defmt::assert_eq!(health.feed(0x55), Err(HealthTestFailure::RepetitionCount));
+
+    // One value in 9 of every 10 samples, never repeated long enough for the
+    // repetition count test, trips the adaptive proportion test
+    let mut health = HealthTests::new();
+    let mut result = Ok(());
+    for i in 0..APT_WINDOW {
+        let sample = if i % 10 == 9 {
+            noise(&mut seed) | 0x80
+        } else {
+            0x07
+        };
+        result = health.feed(sample);
+        if result.is_err() {
+            break;
+        }
+    }
+    defmt::assert_eq!(result, Err(HealthTestFailure::AdaptiveProportion));
+
+    health.restart();
+    defmt::assert!(health.failure().is_none());
+    info!("injected patterns detected");
+
+    // The real source goes through the same tests
+    let mut buf = [0u8; 64];
+    cracen.rng(&mut buf).unwrap();
+    info!("buf: {:02x}", buf);
+
+    loop {
+        cortex_m::asm::nop();
+    }
+}
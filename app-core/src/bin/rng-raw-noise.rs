@@ -0,0 +1,42 @@
+#![no_std]
+#![no_main]
+
+use core::ops::ControlFlow;
+
+use app_core::Cracen;
+use cortex_m_rt::entry;
+use defmt::info;
+use defmt_rtt as _;
+use panic_probe as _;
+
+// 1M samples of 8 bits, the minimum SP 800-90B asks for
+const WORDS: usize = 1_000_000 / 4;
+const CLOCK_DIVIDER: u32 = 0;
+
+// Dumps raw noise words over RTT. Capture with e.g.
+// `probe-rs run ... > noise.log` and strip the defmt framing offline.
+#[entry]
+fn main() -> ! {
+    info!("Starting nRF54L15 RNG raw noise export...");
+    let p = nrf54l15_app_pac::Peripherals::take().unwrap();
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
+
+    let mut sent = 0;
+    cracen
+        .rng_raw_noise(CLOCK_DIVIDER, |words| {
+            let take = words.len().min(WORDS - sent);
+            defmt::println!("noise {=[u32]:08x}", words[..take]);
+            sent += take;
+            if sent == WORDS {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .unwrap();
+    info!("exported {} noise words", sent);
+
+    loop {
+        cortex_m::asm::nop();
+    }
+}
@@ -1,6 +1,7 @@
 #![no_std]
 #![no_main]
 
+use app_core::Cracen;
 use cortex_m_rt::entry;
 use defmt::info;
 use defmt_rtt as _;
@@ -10,15 +11,10 @@ use panic_probe as _;
 fn main() -> ! {
     info!("Starting nRF54L15 RNG buffer example...");
     let p = nrf54l15_app_pac::Peripherals::take().unwrap();
-
-    p.global_cracen_s.enable().write(|w| {
-        w.rng().set_bit();
-        w.cryptomaster().set_bit();
-        w.pkeikg().set_bit()
-    });
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
 
     let mut buf = [0u8; 64];
-    app_core::rng(&p, &mut buf);
+    cracen.rng(&mut buf).unwrap();
 
     info!("RNG buffer:");
     info!("buf: {:02x}", buf);
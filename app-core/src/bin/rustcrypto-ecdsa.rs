@@ -1,20 +1,24 @@
 #![no_std]
 #![no_main]
 
+use app_core::{Cracen, CracenRng};
 use cortex_m_rt::entry;
 use defmt::info;
 use defmt_rtt as _;
-use nrf54l15_app_pac as _;
 use panic_probe as _;
 
 use p256::{
     SecretKey,
-    ecdsa::{Signature, SigningKey, signature::Signer, signature::Verifier},
+    ecdsa::{
+        Signature, SigningKey, signature::RandomizedSigner, signature::Signer, signature::Verifier,
+    },
 };
 
 #[entry]
 fn main() -> ! {
     info!("ECDSA no_std signing example...");
+    let p = nrf54l15_app_pac::Peripherals::take().unwrap();
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
 
     let msg = b"example";
 
@@ -43,6 +47,18 @@ fn main() -> ! {
         Err(_) => info!("Signature verification failed"),
     }
 
+    // Fresh key and hedged signature, with entropy from the CRACEN TRNG
+    let mut rng = CracenRng::new(&mut cracen);
+    let secret = SecretKey::random(&mut rng);
+    let signing_key = SigningKey::from(&secret);
+    let sig: Signature = signing_key.sign_with_rng(&mut rng, msg.as_slice());
+    info!("random key signature = {:02x}", sig.to_bytes().as_slice());
+
+    match signing_key.verifying_key().verify(msg.as_slice(), &sig) {
+        Ok(()) => info!("Random key signature verified successfully"),
+        Err(_) => info!("Random key signature verification failed"),
+    }
+
     loop {
         cortex_m::asm::nop();
     }
@@ -1,28 +1,81 @@
 #![no_std]
 #![no_main]
 
+use app_core::{Cracen, CracenError, CracenSha256, CracenSha512, SharedCracen};
 use cortex_m_rt::entry;
 use defmt::info;
 use defmt_rtt as _;
-use nrf54l15_app_pac as _;
+use digest::{FixedOutputReset, Output, Reset, Update};
+use hmac::{Mac, SimpleHmac};
 use panic_probe as _;
-use sha2::{Digest, Sha256};
+use sha2::{Digest, Sha256, Sha512};
+
+// RFC 4231 4.3, test case 2
+const HMAC_KEY: &[u8] = b"Jefe";
+const HMAC_MSG: &[u8] = b"what do ya want for nothing?";
+const HMAC_SHA256: [u8; 32] = [
+    0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95, 0x75, 0xc7,
+    0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9, 0x64, 0xec, 0x38, 0x43,
+];
 
 #[entry]
 fn main() -> ! {
-    // Start
+    let p = nrf54l15_app_pac::Peripherals::take().unwrap();
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
+
+    // The hardware HMAC agrees with RustCrypto's over the software hash
     let msg = b"example";
-    // Create a hasher and feed data
-    let mut hasher = Sha256::new();
-    hasher.update(msg);
-    // Read result
-    let digest = hasher.finalize();
+    let sw_mac = SimpleHmac::<Sha256>::new_from_slice(b"key")
+        .unwrap()
+        .chain_update(msg);
+    let mut tag = [0u8; 32];
+    cracen.hmac_sha256(b"key", msg, &mut tag).unwrap();
+    info!("CRACEN HMAC-SHA256 = {:x}", tag);
+    sw_mac.verify_slice(&tag).unwrap();
+
+    // The `digest` front-ends hash on a registered `SharedCracen`
+    let shared = cortex_m::singleton!(: SharedCracen = SharedCracen::new(cracen)).unwrap();
+    defmt::assert_eq!(
+        CracenSha256::new().chain(msg).try_finalize().err(),
+        Some(CracenError::InvalidInput)
+    );
+    shared.use_for_digests();
+
+    let digest = Sha256::digest(msg);
+    info!("SHA256(example) = {:x}", digest.as_slice());
+    let mut hasher = CracenSha256::new();
+    Update::update(&mut hasher, msg);
+    let cracen_digest = hasher.try_finalize().unwrap();
+    info!("CRACEN SHA256 = {:x}", cracen_digest.as_slice());
+    defmt::assert_eq!(digest.as_slice(), cracen_digest.as_slice());
 
-    // Finish
+    let digest = Sha512::digest(msg);
+    let cracen_digest = digest_generic::<CracenSha512>(msg);
+    defmt::assert_eq!(digest.as_slice(), cracen_digest.as_slice());
 
-    info!("SHA256(AAA) = {:x}", digest.as_slice());
+    // Resetting starts over on the next message
+    let mut hasher = CracenSha256::new();
+    Update::update(&mut hasher, b"something else");
+    Reset::reset(&mut hasher);
+    Update::update(&mut hasher, msg);
+    let cracen_digest = hasher.finalize_fixed_reset();
+    defmt::assert!(hasher.error().is_none());
+    defmt::assert_eq!(Sha256::digest(msg).as_slice(), cracen_digest.as_slice());
+
+    // RustCrypto's HMAC over the CRACEN hash
+    let mut mac = SimpleHmac::<CracenSha256>::new_from_slice(HMAC_KEY).unwrap();
+    Mac::update(&mut mac, HMAC_MSG);
+    let tag = mac.clone().finalize().into_bytes();
+    info!("SimpleHmac<CracenSha256> = {:x}", tag.as_slice());
+    defmt::assert_eq!(tag.as_slice(), &HMAC_SHA256);
+    mac.verify_slice(&HMAC_SHA256).unwrap();
 
     loop {
         cortex_m::asm::nop();
     }
 }
+
+// Stands in for any code written against the `digest` traits
+fn digest_generic<D: Digest>(msg: &[u8]) -> Output<D> {
+    D::new().chain_update(msg).finalize()
+}
@@ -0,0 +1,104 @@
+#![no_std]
+#![no_main]
+
+use app_core::{Cracen, Sha3Alg, Sha3State};
+use cortex_m_rt::entry;
+use defmt::info;
+use defmt_rtt as _;
+use panic_probe as _;
+
+#[entry]
+fn main() -> ! {
+    info!("Starting nRF54L15 CryptoMaster SHA-3 example...");
+    let p = nrf54l15_app_pac::Peripherals::take().unwrap();
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
+
+    let input = b"abc";
+    let mut expected = [0u8; 64];
+
+    let mut out = [0u8; 28];
+    cracen.sha3_224(input, &mut out).unwrap();
+    hex::decode_to_slice(
+        "e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf",
+        &mut expected[..28],
+    )
+    .unwrap();
+    defmt::assert_eq!(out, expected[..28]);
+
+    let mut out = [0u8; 32];
+    cracen.sha3_256(input, &mut out).unwrap();
+    hex::decode_to_slice(
+        "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532",
+        &mut expected[..32],
+    )
+    .unwrap();
+    defmt::assert_eq!(out, expected[..32]);
+
+    let mut out = [0u8; 48];
+    cracen.sha3_384(input, &mut out).unwrap();
+    hex::decode_to_slice(
+        "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b298d88cea927ac7f539f1edf228376d25",
+        &mut expected[..48],
+    )
+    .unwrap();
+    defmt::assert_eq!(out, expected[..48]);
+
+    let mut out = [0u8; 64];
+    cracen.sha3_512(input, &mut out).unwrap();
+    hex::decode_to_slice(
+        "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0",
+        &mut expected,
+    )
+    .unwrap();
+    defmt::assert_eq!(out, expected);
+
+    let mut out = [0u8; 32];
+    cracen.keccak256(input, &mut out).unwrap();
+    hex::decode_to_slice(
+        "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+        &mut expected[..32],
+    )
+    .unwrap();
+    defmt::assert_eq!(out, expected[..32]);
+    info!("SHA-3 fixed-output vectors OK");
+
+    let mut out = [0u8; 32];
+    cracen.shake128(b"", &mut out).unwrap();
+    hex::decode_to_slice(
+        "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26",
+        &mut expected[..32],
+    )
+    .unwrap();
+    defmt::assert_eq!(out, expected[..32]);
+
+    let mut out = [0u8; 64];
+    cracen.shake256(b"", &mut out).unwrap();
+    hex::decode_to_slice(
+        "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762fd75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be",
+        &mut expected,
+    )
+    .unwrap();
+    defmt::assert_eq!(out, expected);
+
+    // Squeeze 400 bytes of SHAKE128 (more than two rate blocks) in uneven
+    // reads and check the tail
+    let mut state = Sha3State::init(Sha3Alg::Shake128);
+    state.update(&mut cracen, b"a").unwrap();
+    state.update(&mut cracen, b"bc").unwrap();
+    let mut reader = state.finalize_xof(&mut cracen).unwrap();
+    let mut stream = [0u8; 400];
+    let (first, rest) = stream.split_at_mut(100);
+    reader.read(&mut cracen, first).unwrap();
+    reader.read(&mut cracen, rest).unwrap();
+    hex::decode_to_slice(
+        "35d6dbb75651b284076f5fde47b4a0586ee173e30bd4d08f2bc59c6114bdd745",
+        &mut expected[..32],
+    )
+    .unwrap();
+    defmt::assert_eq!(stream[368..], expected[..32]);
+    info!("SHAKE vectors OK");
+
+    loop {
+        cortex_m::asm::nop();
+    }
+}
@@ -0,0 +1,86 @@
+#![no_std]
+#![no_main]
+
+use app_core::{Cracen, HashAlg, HashState, SharedCracen};
+use defmt::info;
+use embassy_executor::Spawner;
+use embassy_nrf::bind_interrupts;
+use embassy_time::Timer;
+use {defmt_rtt as _, panic_probe as _};
+
+bind_interrupts!(struct Irqs {
+    CRACEN => app_core::InterruptHandler;
+});
+
+static LOG: [u8; 4 * 1024] = [b'l'; 4 * 1024];
+
+// Private key 2 and its public key 2G on P-256
+const PRIV_KEY: [u8; 32] = {
+    let mut k = [0u8; 32];
+    k[31] = 0x02;
+    k
+};
+const PUB_KEY_X: [u8; 32] = [
+    0x7c, 0xf2, 0x7b, 0x18, 0x8d, 0x03, 0x4f, 0x7e, 0x8a, 0x52, 0x38, 0x03, 0x04, 0xb5, 0x1a, 0xc3,
+    0xc0, 0x89, 0x69, 0xe2, 0x77, 0xf2, 0x1b, 0x35, 0xa6, 0x0b, 0x48, 0xfc, 0x47, 0x66, 0x99, 0x78,
+];
+const PUB_KEY_Y: [u8; 32] = [
+    0x07, 0x77, 0x55, 0x10, 0xdb, 0x8e, 0xd0, 0x40, 0x29, 0x3d, 0x9a, 0xc6, 0x9f, 0x74, 0x30, 0xdb,
+    0xba, 0x7d, 0xad, 0xe6, 0x3c, 0xe9, 0x82, 0x29, 0x9e, 0x04, 0xb7, 0x9d, 0x22, 0x78, 0x73, 0xd1,
+];
+
+// Stands in for the BLE task: signs and checks a message over and over
+#[embassy_executor::task]
+async fn signer(cracen: &'static SharedCracen) {
+    for i in 0..10 {
+        let (r, s) = cracen.ecdsa_sign(b"example", &PRIV_KEY).await.unwrap();
+        cracen
+            .ecdsa_verify(b"example", &r, &s, &PUB_KEY_X, &PUB_KEY_Y)
+            .await
+            .unwrap();
+        info!("signature {} verified", i);
+        Timer::after_millis(1).await;
+    }
+}
+
+// Stands in for the logging task: hashes a large buffer in chunks
+#[embassy_executor::task]
+async fn logger(cracen: &'static SharedCracen) {
+    let mut expected = [0u8; 32];
+    cracen
+        .hash(&LOG, &mut expected, HashAlg::Sha2_256)
+        .await
+        .unwrap();
+
+    for i in 0..10 {
+        let mut state = HashState::init(HashAlg::Sha2_256);
+        for chunk in LOG.chunks(500) {
+            cracen.update(&mut state, chunk).await.unwrap();
+            Timer::after_micros(100).await;
+        }
+        let mut out = [0u8; 32];
+        cracen.finalize(&mut state, &mut out).await.unwrap();
+        defmt::assert_eq!(out, expected);
+        info!("log digest {} matches", i);
+    }
+}
+
+#[embassy_executor::main]
+async fn main(spawner: Spawner) {
+    info!("Starting nRF54L15 shared CRACEN example...");
+    let _ = embassy_nrf::init(Default::default());
+    let p = nrf54l15_app_pac::Peripherals::take().unwrap();
+    app_core::cracen_enable_interrupt(Irqs);
+
+    let cracen: &'static SharedCracen = cortex_m::singleton!(
+        : SharedCracen = SharedCracen::new(Cracen::new(p.global_cracen_s, p.global_cracencore_s))
+    )
+    .unwrap();
+
+    spawner.spawn(signer(cracen)).unwrap();
+    spawner.spawn(logger(cracen)).unwrap();
+
+    loop {
+        Timer::after_secs(1).await;
+    }
+}
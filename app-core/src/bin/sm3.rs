@@ -0,0 +1,57 @@
+#![no_std]
+#![no_main]
+
+use app_core::{Cracen, HashAlg, HashState};
+use cortex_m_rt::entry;
+use defmt::info;
+use defmt_rtt as _;
+use panic_probe as _;
+
+// GB/T 32905-2016 Appendix A
+const KAT: [(&[u8], &str); 3] = [
+    (
+        b"abc",
+        "66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0",
+    ),
+    (
+        b"abcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcd",
+        "debe9ff92275b8a138604889c18e5a4d6fdb70e5387e5765293dcba39c0c5732",
+    ),
+    // Multi-block message to exercise the incremental path
+    (
+        &[b'a'; 300],
+        "06ea971838e9ea6603795167c74ccbf8c3081a88355bd10c0a0f285d6343e5bd",
+    ),
+];
+
+#[entry]
+fn main() -> ! {
+    info!("Starting nRF54L15 CryptoMaster SM3 example...");
+    let p = nrf54l15_app_pac::Peripherals::take().unwrap();
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
+
+    for (msg, digest_hex) in KAT {
+        let mut expected = [0u8; 32];
+        hex::decode_to_slice(digest_hex, &mut expected).unwrap();
+
+        let mut out = [0u8; 32];
+        cracen.sm3(msg, &mut out).unwrap();
+        info!("SM3 one-shot: {:02x}", out);
+        defmt::assert_eq!(out, expected);
+
+        for split in [1, msg.len() / 2, msg.len() - 1] {
+            let mut state = HashState::init(HashAlg::Sm3);
+            state.update(&mut cracen, &msg[..split]).unwrap();
+            state.update(&mut cracen, &msg[split..]).unwrap();
+            let mut out = [0u8; 32];
+            state.finalize(&mut cracen, &mut out).unwrap();
+            defmt::assert_eq!(out, expected, "split at {}", split);
+        }
+    }
+
+    info!("SM3 known-answer tests passed");
+
+    loop {
+        cortex_m::asm::nop();
+    }
+}
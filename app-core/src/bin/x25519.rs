@@ -0,0 +1,122 @@
+#![no_std]
+#![no_main]
+
+use app_core::Cracen;
+use cortex_m_rt::entry;
+use defmt::info;
+use defmt_rtt as _;
+use panic_probe as _;
+
+// RFC 7748 5.2
+const SCALAR_1: [u8; 32] = [
+    0xa5, 0x46, 0xe3, 0x6b, 0xf0, 0x52, 0x7c, 0x9d, 0x3b, 0x16, 0x15, 0x4b, 0x82, 0x46, 0x5e, 0xdd,
+    0x62, 0x14, 0x4c, 0x0a, 0xc1, 0xfc, 0x5a, 0x18, 0x50, 0x6a, 0x22, 0x44, 0xba, 0x44, 0x9a, 0xc4,
+];
+const U_1: [u8; 32] = [
+    0xe6, 0xdb, 0x68, 0x67, 0x58, 0x30, 0x30, 0xdb, 0x35, 0x94, 0xc1, 0xa4, 0x24, 0xb1, 0x5f, 0x7c,
+    0x72, 0x66, 0x24, 0xec, 0x26, 0xb3, 0x35, 0x3b, 0x10, 0xa9, 0x03, 0xa6, 0xd0, 0xab, 0x1c, 0x4c,
+];
+const OUT_1: [u8; 32] = [
+    0xc3, 0xda, 0x55, 0x37, 0x9d, 0xe9, 0xc6, 0x90, 0x8e, 0x94, 0xea, 0x4d, 0xf2, 0x8d, 0x08, 0x4f,
+    0x32, 0xec, 0xcf, 0x03, 0x49, 0x1c, 0x71, 0xf7, 0x54, 0xb4, 0x07, 0x55, 0x77, 0xa2, 0x85, 0x52,
+];
+const SCALAR_2: [u8; 32] = [
+    0x4b, 0x66, 0xe9, 0xd4, 0xd1, 0xb4, 0x67, 0x3c, 0x5a, 0xd2, 0x26, 0x91, 0x95, 0x7d, 0x6a, 0xf5,
+    0xc1, 0x1b, 0x64, 0x21, 0xe0, 0xea, 0x01, 0xd4, 0x2c, 0xa4, 0x16, 0x9e, 0x79, 0x18, 0xba, 0x0d,
+];
+const U_2: [u8; 32] = [
+    0xe5, 0x21, 0x0f, 0x12, 0x78, 0x68, 0x11, 0xd3, 0xf4, 0xb7, 0x95, 0x9d, 0x05, 0x38, 0xae, 0x2c,
+    0x31, 0xdb, 0xe7, 0x10, 0x6f, 0xc0, 0x3c, 0x3e, 0xfc, 0x4c, 0xd5, 0x49, 0xc7, 0x15, 0xa4, 0x93,
+];
+const OUT_2: [u8; 32] = [
+    0x95, 0xcb, 0xde, 0x94, 0x76, 0xe8, 0x90, 0x7d, 0x7a, 0xad, 0xe4, 0x5c, 0xb4, 0xb8, 0x73, 0xf8,
+    0x8b, 0x59, 0x5a, 0x68, 0x79, 0x9f, 0xa1, 0x52, 0xe6, 0xf8, 0xf7, 0x64, 0x7a, 0xac, 0x79, 0x57,
+];
+
+// RFC 7748 5.2, iterated test after 1 and 1,000 iterations
+const ITER_1: [u8; 32] = [
+    0x42, 0x2c, 0x8e, 0x7a, 0x62, 0x27, 0xd7, 0xbc, 0xa1, 0x35, 0x0b, 0x3e, 0x2b, 0xb7, 0x27, 0x9f,
+    0x78, 0x97, 0xb8, 0x7b, 0xb6, 0x85, 0x4b, 0x78, 0x3c, 0x60, 0xe8, 0x03, 0x11, 0xae, 0x30, 0x79,
+];
+const ITER_1000: [u8; 32] = [
+    0x68, 0x4c, 0xf5, 0x9b, 0xa8, 0x33, 0x09, 0x55, 0x28, 0x00, 0xef, 0x56, 0x6f, 0x2f, 0x4d, 0x3c,
+    0x1c, 0x38, 0x87, 0xc4, 0x93, 0x60, 0xe3, 0x87, 0x5f, 0x2e, 0xb9, 0x4d, 0x99, 0x53, 0x2c, 0x51,
+];
+
+// RFC 7748 6.1
+const ALICE_PRIV: [u8; 32] = [
+    0x77, 0x07, 0x6d, 0x0a, 0x73, 0x18, 0xa5, 0x7d, 0x3c, 0x16, 0xc1, 0x72, 0x51, 0xb2, 0x66, 0x45,
+    0xdf, 0x4c, 0x2f, 0x87, 0xeb, 0xc0, 0x99, 0x2a, 0xb1, 0x77, 0xfb, 0xa5, 0x1d, 0xb9, 0x2c, 0x2a,
+];
+const ALICE_PUB: [u8; 32] = [
+    0x85, 0x20, 0xf0, 0x09, 0x89, 0x30, 0xa7, 0x54, 0x74, 0x8b, 0x7d, 0xdc, 0xb4, 0x3e, 0xf7, 0x5a,
+    0x0d, 0xbf, 0x3a, 0x0d, 0x26, 0x38, 0x1a, 0xf4, 0xeb, 0xa4, 0xa9, 0x8e, 0xaa, 0x9b, 0x4e, 0x6a,
+];
+const BOB_PRIV: [u8; 32] = [
+    0x5d, 0xab, 0x08, 0x7e, 0x62, 0x4a, 0x8a, 0x4b, 0x79, 0xe1, 0x7f, 0x8b, 0x83, 0x80, 0x0e, 0xe6,
+    0x6f, 0x3b, 0xb1, 0x29, 0x26, 0x18, 0xb6, 0xfd, 0x1c, 0x2f, 0x8b, 0x27, 0xff, 0x88, 0xe0, 0xeb,
+];
+const BOB_PUB: [u8; 32] = [
+    0xde, 0x9e, 0xdb, 0x7d, 0x7b, 0x7d, 0xc1, 0xb4, 0xd3, 0x5b, 0x61, 0xc2, 0xec, 0xe4, 0x35, 0x37,
+    0x3f, 0x83, 0x43, 0xc8, 0x5b, 0x78, 0x67, 0x4d, 0xad, 0xfc, 0x7e, 0x14, 0x6f, 0x88, 0x2b, 0x4f,
+];
+const SHARED: [u8; 32] = [
+    0x4a, 0x5d, 0x9d, 0x5b, 0xa4, 0xce, 0x2d, 0xe1, 0x72, 0x8e, 0x3b, 0xf4, 0x80, 0x35, 0x0f, 0x25,
+    0xe0, 0x7e, 0x21, 0xc9, 0x47, 0xd1, 0x9e, 0x33, 0x76, 0xf0, 0x9b, 0x3c, 0x1e, 0x16, 0x17, 0x42,
+];
+
+// RFC 7748 5: u-coordinates of 2^255-19 and up are non-canonical and must be
+// reduced modulo p, after the top bit is masked. 9 + p is the base point.
+const U_BASE_NON_CANONICAL: [u8; 32] = [
+    0xf6, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f,
+];
+
+#[entry]
+fn main() -> ! {
+    info!("Starting nRF54L15 X25519 example...");
+    let p = nrf54l15_app_pac::Peripherals::take().unwrap();
+    let mut cracen = Cracen::new(p.global_cracen_s, p.global_cracencore_s);
+
+    assert_eq!(cracen.x25519(&SCALAR_1, &U_1).unwrap(), OUT_1);
+    assert_eq!(cracen.x25519(&SCALAR_2, &U_2).unwrap(), OUT_2);
+    info!("Function vectors OK");
+
+    // k = X25519(k, u), u = old k, starting from k = u = 9
+    let mut k = app_core::X25519_BASE_POINT;
+    let mut u = app_core::X25519_BASE_POINT;
+    for i in 1..=1000 {
+        let next = cracen.x25519(&k, &u).unwrap();
+        u = k;
+        k = next;
+        match i {
+            1 => assert_eq!(k, ITER_1),
+            1000 => assert_eq!(k, ITER_1000),
+            _ => {}
+        }
+    }
+    info!("Iterated test OK");
+
+    assert_eq!(cracen.x25519_base(&ALICE_PRIV).unwrap(), ALICE_PUB);
+    assert_eq!(cracen.x25519_base(&BOB_PRIV).unwrap(), BOB_PUB);
+    assert_eq!(cracen.x25519(&ALICE_PRIV, &BOB_PUB).unwrap(), SHARED);
+    assert_eq!(cracen.x25519(&BOB_PRIV, &ALICE_PUB).unwrap(), SHARED);
+    info!("Diffie-Hellman OK");
+
+    assert_eq!(
+        cracen.x25519(&ALICE_PRIV, &U_BASE_NON_CANONICAL).unwrap(),
+        ALICE_PUB
+    );
+    // 2^256 - 1 is 2^255 - 1 once masked, which is 18 modulo p
+    let mut u_18 = [0u8; 32];
+    u_18[0] = 18;
+    assert_eq!(
+        cracen.x25519(&BOB_PRIV, &[0xff; 32]).unwrap(),
+        cracen.x25519(&BOB_PRIV, &u_18).unwrap()
+    );
+    info!("Non-canonical u OK");
+
+    loop {
+        cortex_m::asm::nop();
+    }
+}
@@ -0,0 +1,176 @@
+//! Owned handle to the CRACEN peripheral.
+
+use core::sync::atomic::{AtomicU8, AtomicU32, Ordering};
+
+use nrf54l15_app_pac::{GlobalCracenS, GlobalCracencoreS};
+
+use crate::{CracenError, load_microcode, pk_wait};
+
+/// Exclusive handle to CRACEN and its CryptoMaster, RNG and PK engines.
+///
+/// Takes the two PAC singletons once. Every hash, RNG and PK operation is a
+/// `&mut self` method, so two overlapping users of the engines are rejected
+/// at compile time. Each sub-engine is only powered while an operation that
+/// needs it is in flight.
+pub struct Cracen {
+    pub(crate) global_cracen_s: GlobalCracenS,
+    pub(crate) global_cracencore_s: GlobalCracencoreS,
+    // Engines this handle may drive, one bit per `Engine`. All of them,
+    // unless the handle is a part made by `split`.
+    engines: u8,
+}
+
+const ALL_ENGINES: u8 = 0b111;
+
+impl Cracen {
+    pub fn new(global_cracen_s: GlobalCracenS, global_cracencore_s: GlobalCracencoreS) -> Self {
+        Self {
+            global_cracen_s,
+            global_cracencore_s,
+            engines: ALL_ENGINES,
+        }
+    }
+
+    /// Gives the PAC singletons back.
+    pub fn free(self) -> (GlobalCracenS, GlobalCracencoreS) {
+        (self.global_cracen_s, self.global_cracencore_s)
+    }
+
+    /// Splits the handle into one part per engine, in [`Engine`] order, so
+    /// [`SharedCracen`](crate::SharedCracen) can lock them separately.
+    ///
+    /// Every part holds its own copy of the PAC singletons. That is sound
+    /// because [`power`](Self::power) only lets a part drive its own engine,
+    /// and the few registers the engines share (`ENABLE` and the interrupt
+    /// bits) are only written inside critical sections or by the interrupt
+    /// handler.
+    pub(crate) fn split(self) -> [Cracen; 3] {
+        [Engine::CryptoMaster, Engine::Rng, Engine::Pke].map(|engine| Self {
+            global_cracen_s: unsafe { GlobalCracenS::steal() },
+            global_cracencore_s: unsafe { GlobalCracencoreS::steal() },
+            engines: engine.bit(),
+        })
+    }
+
+    /// Puts the parts made by [`split`](Self::split) back together.
+    pub(crate) fn join(parts: [Cracen; 3]) -> Self {
+        let engines = parts.iter().fold(0, |engines, part| engines | part.engines);
+        assert_eq!(engines, ALL_ENGINES, "joining an incomplete split");
+        let [part, ..] = parts;
+        Self {
+            engines: ALL_ENGINES,
+            ..part
+        }
+    }
+
+    /// Powers `engine` until the returned guard is dropped.
+    ///
+    /// Bringing the PK engine up also reloads its microcode, which does not
+    /// survive power-down, and waits for the IKG DRBG to finish seeding.
+    ///
+    /// Panics if `engine` belongs to another part of a split handle.
+    pub(crate) fn power(&self, engine: Engine) -> Result<PowerGuard, CracenError> {
+        assert!(
+            self.engines & engine.bit() != 0,
+            "{:?} belongs to another part of the handle",
+            engine
+        );
+        if engine == Engine::Pke {
+            // The IKG draws on the RNG for its seed and blinding values
+            power_up(Engine::Rng);
+            let guard = PowerGuard(Engine::Pke);
+            if power_up(Engine::Pke) {
+                unsafe { load_microcode() };
+                pk_wait(&self.global_cracencore_s)?;
+            }
+            return Ok(guard);
+        }
+
+        power_up(engine);
+        Ok(PowerGuard(engine))
+    }
+
+    /// Keeps the RNG powered until the guard is dropped, so the noise source
+    /// does not restart on every call. Unlike
+    /// the PK engine the RNG needs no setup, so this cannot fail.
+    pub(crate) fn power_rng(&self) -> PowerGuard {
+        assert!(
+            self.engines & Engine::Rng.bit() != 0,
+            "Rng belongs to another part of the handle"
+        );
+        power_up(Engine::Rng);
+        PowerGuard(Engine::Rng)
+    }
+}
+
+/// CRACEN sub-blocks with their own bit in `ENABLE`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub(crate) enum Engine {
+    CryptoMaster,
+    Rng,
+    Pke,
+}
+
+impl Engine {
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+// Number of operations in flight per engine, indexed by `Engine`. Counted in
+// statics rather than in the handle because the parts of a split handle
+// share them, and the PK engine keeps the RNG powered too.
+static USERS: [AtomicU8; 3] = [AtomicU8::new(0), AtomicU8::new(0), AtomicU8::new(0)];
+
+/// Keeps an [`Engine`] powered; the last guard to go turns it off.
+pub(crate) struct PowerGuard(Engine);
+
+impl Drop for PowerGuard {
+    fn drop(&mut self) {
+        power_down(self.0);
+        if self.0 == Engine::Pke {
+            power_down(Engine::Rng);
+        }
+    }
+}
+
+// Bumped every time the RNG is switched on, so the health tests know the
+// sample stream has been interrupted
+static RNG_POWER_CYCLES: AtomicU32 = AtomicU32::new(0);
+
+pub(crate) fn rng_power_cycles() -> u32 {
+    RNG_POWER_CYCLES.load(Ordering::Relaxed)
+}
+
+// Returns true if the engine was off until now
+fn power_up(engine: Engine) -> bool {
+    critical_section::with(|_| {
+        let first = USERS[engine as usize].fetch_add(1, Ordering::Relaxed) == 0;
+        if first {
+            set_enabled(engine, true);
+            if engine == Engine::Rng {
+                RNG_POWER_CYCLES.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        first
+    })
+}
+
+fn power_down(engine: Engine) {
+    critical_section::with(|_| {
+        if USERS[engine as usize].fetch_sub(1, Ordering::Relaxed) == 1 {
+            set_enabled(engine, false);
+        }
+    })
+}
+
+// Read-modify-write so the other engines' bits are left alone; callers hold
+// the critical section
+fn set_enabled(engine: Engine, on: bool) {
+    let cracen = unsafe { GlobalCracenS::steal() };
+    cracen.enable().modify(|_, w| match engine {
+        Engine::CryptoMaster => w.cryptomaster().bit(on),
+        Engine::Rng => w.rng().bit(on),
+        Engine::Pke => w.pkeikg().bit(on),
+    });
+}
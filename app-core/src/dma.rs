@@ -0,0 +1,519 @@
+//! CryptoMaster DMA descriptor chains.
+//!
+//! The fetch and push DMAs walk linked lists of [`SxDesc`]. Each descriptor
+//! carries a tag telling the CryptoMaster which engine the bytes go to and
+//! what they are (configuration header, saved state, payload), and how many
+//! bytes of the last word to ignore. [`DescriptorChain`] builds both lists
+//! from borrowed buffers. The encoding helpers are plain `const fn`s with no
+//! hardware access, so the chains can be checked off-target.
+
+use core::future::poll_fn;
+use core::marker::PhantomData;
+use core::task::Poll;
+
+use embassy_nrf::interrupt::typelevel::{self, Binding, Interrupt as _};
+use embassy_sync::waitqueue::AtomicWaker;
+use nrf54l15_app_pac::{GlobalCracenS, GlobalCracencoreS};
+
+use crate::{Cracen, CracenError, Engine, wait_until};
+
+/// Engine select: pass-through, used on the push side
+pub const TAG_ENGINE_BYPASS: u32 = 0x00;
+/// Engine select: BA413 hash engine (SHA-1, SHA-2, SM3)
+pub const TAG_ENGINE_BA413: u32 = 0x03;
+/// Engine select: BA418 Keccak engine (SHA-3, SHAKE)
+pub const TAG_ENGINE_BA418: u32 = 0x05;
+/// Engine configuration header
+pub const TAG_CONFIG: u32 = 0x10;
+/// Last descriptor of a run of the same data type
+pub const TAG_LAST: u32 = 0x20;
+/// Saved state (or key) loaded before the payload
+pub const TAG_STATE: u32 = 0x40;
+
+/// Ignore the last `n` bytes of the final word of a descriptor.
+pub const fn tag_ignore_bytes(n: u32) -> u32 {
+    n << 8
+}
+
+/// Length flag: realign the DMA to a word boundary after this descriptor
+pub const SZ_REALIGN: u32 = 0x2000_0000;
+
+pub const TAG_HASH_HEADER: u32 = TAG_CONFIG | TAG_ENGINE_BA413;
+pub const TAG_HASH_DATA: u32 = TAG_ENGINE_BA413;
+pub const TAG_HASH_DATA_LAST: u32 = TAG_LAST | TAG_ENGINE_BA413;
+pub const TAG_HASH_STATE: u32 = TAG_STATE | TAG_LAST | TAG_ENGINE_BA413;
+pub const TAG_SINK: u32 = TAG_LAST | TAG_ENGINE_BYPASS;
+
+pub const MAX_FETCH_DESCRIPTORS: usize = 10;
+pub const MAX_SINK_DESCRIPTORS: usize = 2;
+
+#[allow(
+    clippy::manual_dangling_ptr,
+    reason = "nRF54L15 uses 1 as last-descriptor sentinel"
+)]
+const LAST_DESC_PTR: *mut SxDesc = 1 as *mut SxDesc;
+
+const EMPTY_DESC: SxDesc = SxDesc {
+    addr: core::ptr::null_mut(),
+    next: LAST_DESC_PTR,
+    sz: 0,
+    dmatag: 0,
+};
+
+// Fetched in place of an empty buffer, all four bytes ignored
+static ZERO_WORD: [u8; 4] = [0; 4];
+
+// #[repr(C)]
+#[repr(C, align(32))]
+#[derive(Debug, Clone, Copy, defmt::Format)]
+pub struct SxDesc {
+    pub addr: *mut u8,
+    pub next: *mut SxDesc,
+    pub sz: u32,
+    pub dmatag: u32,
+}
+
+/// `sz` word for `len` bytes streamed as they are. The end of a run
+/// (`TAG_LAST`) and configuration headers realign, so whatever follows
+/// starts on a word boundary.
+pub const fn fetch_sz(len: usize, tag: u32) -> u32 {
+    if tag & (TAG_LAST | TAG_CONFIG) != 0 {
+        SZ_REALIGN | len as u32
+    } else {
+        len as u32
+    }
+}
+
+/// `(sz, dmatag)` for `len` bytes rounded up to whole words, with the
+/// padding marked as ignored. An empty buffer is one fully ignored word.
+pub const fn padded_encoding(len: usize, tag: u32) -> (u32, u32) {
+    let words = if len == 0 { 4 } else { len.div_ceil(4) * 4 };
+    (
+        SZ_REALIGN | words as u32,
+        tag | tag_ignore_bytes((words - len) as u32),
+    )
+}
+
+pub fn dmatag_for(input: usize) -> u32 {
+    padded_encoding(input, TAG_HASH_DATA_LAST).1
+}
+
+pub fn sz(n: usize) -> u32 {
+    padded_encoding(n, TAG_ENGINE_BYPASS).0
+}
+
+/// Fetch and push descriptor lists for one CryptoMaster operation.
+///
+/// Descriptors are stored unlinked and borrow their buffers for `'a`; the
+/// `next` pointers are only filled in by [`DescriptorChain::run`], once the
+/// chain no longer moves.
+pub struct DescriptorChain<'a> {
+    fetch: [SxDesc; MAX_FETCH_DESCRIPTORS],
+    fetch_len: usize,
+    sink: [SxDesc; MAX_SINK_DESCRIPTORS],
+    sink_len: usize,
+    _buffers: PhantomData<&'a mut [u8]>,
+}
+
+impl Default for DescriptorChain<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> DescriptorChain<'a> {
+    pub const fn new() -> Self {
+        Self {
+            fetch: [EMPTY_DESC; MAX_FETCH_DESCRIPTORS],
+            fetch_len: 0,
+            sink: [EMPTY_DESC; MAX_SINK_DESCRIPTORS],
+            sink_len: 0,
+            _buffers: PhantomData,
+        }
+    }
+
+    /// Appends `data` to the fetch list byte for byte. Empty buffers are
+    /// skipped, so the caller decides which descriptor carries `TAG_LAST`.
+    pub fn push_fetch(&mut self, data: &'a [u8], tag: u32) -> &mut Self {
+        if !data.is_empty() {
+            self.append_fetch(data.as_ptr(), fetch_sz(data.len(), tag), tag);
+        }
+        self
+    }
+
+    /// Appends `data` rounded up to whole words, telling the engine to
+    /// ignore the padding. Unlike [`push_fetch`](Self::push_fetch) an empty
+    /// buffer still produces a descriptor.
+    pub fn push_fetch_padded(&mut self, data: &'a [u8], tag: u32) -> &mut Self {
+        let (sz, dmatag) = padded_encoding(data.len(), tag);
+        let addr = if data.is_empty() {
+            ZERO_WORD.as_ptr()
+        } else {
+            data.as_ptr()
+        };
+        self.append_fetch(addr, sz, dmatag);
+        self
+    }
+
+    /// Appends `buf` to the push list.
+    pub fn push_sink(&mut self, buf: &'a mut [u8], tag: u32) -> &mut Self {
+        assert!(self.sink_len < MAX_SINK_DESCRIPTORS, "sink list full");
+        self.sink[self.sink_len] = SxDesc {
+            addr: buf.as_mut_ptr(),
+            next: LAST_DESC_PTR,
+            sz: padded_encoding(buf.len(), tag).0,
+            dmatag: tag,
+        };
+        self.sink_len += 1;
+        self
+    }
+
+    pub fn fetch_descriptors(&self) -> &[SxDesc] {
+        &self.fetch[..self.fetch_len]
+    }
+
+    pub fn sink_descriptors(&self) -> &[SxDesc] {
+        &self.sink[..self.sink_len]
+    }
+
+    /// Links both lists and runs them through the CryptoMaster DMA, waiting
+    /// for both directions to finish.
+    pub fn run(mut self, cracen: &mut Cracen) -> Result<(), CracenError> {
+        self.link()?;
+
+        let _power = cracen.power(Engine::CryptoMaster)?;
+        cryptomaster_start(cracen, &mut self.fetch[0], &mut self.sink[0]);
+        cryptomaster_wait(cracen)
+    }
+
+    /// Like [`run`](Self::run), but yields to the executor until the
+    /// CryptoMaster interrupt reports the push DMA has stopped. Needs
+    /// [`cracen_enable_interrupt`].
+    ///
+    /// Dropping the future before it completes blocks until the DMA is done,
+    /// since the engine still owns the borrowed buffers until then.
+    pub async fn run_async(mut self, cracen: &mut Cracen) -> Result<(), CracenError> {
+        self.link()?;
+
+        // Declared before `guard` so the engine stays powered until it is done
+        let _power = cracen.power(Engine::CryptoMaster)?;
+        {
+            let dma = cracen.global_cracencore_s.cryptmstrdma();
+            dma.intstatclr().write(|w| w.pusherstopped().set_bit());
+            cracen.global_cracen_s.events_cryptomaster().reset();
+            // Bus errors wake us up too, the push side may never stop after one
+            dma.intenset().write(|w| {
+                w.pusherstopped().set_bit();
+                w.fetchererror().set_bit();
+                w.pushererror().set_bit()
+            });
+        }
+
+        cryptomaster_start(cracen, &mut self.fetch[0], &mut self.sink[0]);
+        let cracen = &*cracen;
+        let dma = cracen.global_cracencore_s.cryptmstrdma();
+        let guard = WaitOnDrop(cracen);
+
+        poll_fn(|cx| {
+            CRYPTOMASTER_WAKER.register(cx.waker());
+            if !cryptomaster_busy(cracen) || cryptomaster_fault(cracen) {
+                return Poll::Ready(());
+            }
+            // The handler masks the interrupt again before waking us
+            cracen
+                .global_cracen_s
+                .intenset()
+                .write(|w| w.cryptomaster().set_bit());
+            Poll::Pending
+        })
+        .await;
+
+        core::mem::forget(guard);
+        dma.intenclr().write(|w| {
+            w.pusherstopped().set_bit();
+            w.fetchererror().set_bit();
+            w.pushererror().set_bit()
+        });
+        cryptomaster_status(cracen, Ok(()))
+    }
+
+    // Fills in the `next` pointers; the chain must not move afterwards
+    fn link(&mut self) -> Result<(), CracenError> {
+        if self.fetch_len == 0 || self.sink_len == 0 {
+            return Err(CracenError::InvalidInput);
+        }
+        link(&mut self.fetch[..self.fetch_len]);
+        link(&mut self.sink[..self.sink_len]);
+        Ok(())
+    }
+
+    fn append_fetch(&mut self, addr: *const u8, sz: u32, dmatag: u32) {
+        assert!(self.fetch_len < MAX_FETCH_DESCRIPTORS, "fetch list full");
+        self.fetch[self.fetch_len] = SxDesc {
+            addr: addr as *mut u8,
+            next: LAST_DESC_PTR,
+            sz,
+            dmatag,
+        };
+        self.fetch_len += 1;
+    }
+}
+
+fn link(descs: &mut [SxDesc]) {
+    for i in 1..descs.len() {
+        descs[i - 1].next = &mut descs[i];
+    }
+}
+
+// Keeps a cancelled `run_async` from releasing buffers the DMA still uses
+struct WaitOnDrop<'c>(&'c Cracen);
+
+impl Drop for WaitOnDrop<'_> {
+    fn drop(&mut self) {
+        let _ = cryptomaster_wait(self.0);
+    }
+}
+
+static CRYPTOMASTER_WAKER: AtomicWaker = AtomicWaker::new();
+
+/// CRACEN interrupt handler for the async CryptoMaster API.
+///
+/// ```ignore
+/// bind_interrupts!(struct Irqs {
+///     CRACEN => app_core::InterruptHandler;
+/// });
+/// ```
+pub struct InterruptHandler;
+
+impl typelevel::Handler<typelevel::CRACEN> for InterruptHandler {
+    unsafe fn on_interrupt() {
+        // SAFETY: the handler cannot be given the `Cracen` handle, so it
+        // takes its own view of the registers. It only writes the
+        // write-one-to-clear/-set interrupt registers and the event, never
+        // anything the owner of the handle reads back mid-operation, and
+        // `run_async` rechecks the DMA status itself after waking up.
+        let cracen = unsafe { GlobalCracenS::steal() };
+        let cracencore = unsafe { GlobalCracencoreS::steal() };
+
+        cracen.intenclr().write(|w| w.cryptomaster().set_bit());
+        cracen.events_cryptomaster().reset();
+        cracencore
+            .cryptmstrdma()
+            .intstatclr()
+            .write(|w| w.pusherstopped().set_bit());
+
+        // Error bits stay set for `run_async` to report
+        CRYPTOMASTER_WAKER.wake();
+    }
+}
+
+/// Unmasks the CRACEN interrupt in the NVIC. Call once before using any of
+/// the `_async` functions.
+pub fn cracen_enable_interrupt(_irqs: impl Binding<typelevel::CRACEN, InterruptHandler>) {
+    typelevel::CRACEN::unpend();
+    unsafe { typelevel::CRACEN::enable() };
+}
+
+/// Points the CryptoMaster DMA at one fetch/push descriptor chain and starts
+/// both directions.
+fn cryptomaster_start(cracen: &mut Cracen, fetch: &mut SxDesc, push: &mut SxDesc) {
+    let dma = cracen.global_cracencore_s.cryptmstrdma();
+
+    // Drop bus errors left over from a previous run
+    dma.intstatclr().write(|w| {
+        w.fetchererror().set_bit();
+        w.pushererror().set_bit()
+    });
+
+    // Configure DMA source
+    dma.fetchaddrlsb()
+        .write(|w| unsafe { w.bits(fetch as *mut _ as u32) });
+
+    // Configure DMA sink
+    dma.pushaddrlsb()
+        .write(|w| unsafe { w.bits(push as *mut _ as u32) });
+
+    dma.config().write(|w| {
+        w.fetchctrlindirect().set_bit();
+        w.pushctrlindirect().set_bit();
+        w.fetchstop().clear_bit();
+        w.pushstop().clear_bit();
+        w.softrst().clear_bit()
+    });
+
+    // Start DMA
+    dma.start().write(|w| {
+        w.startfetch().set_bit();
+        w.startpush().set_bit()
+    });
+}
+
+fn cryptomaster_busy(cracen: &Cracen) -> bool {
+    let status = cracen.global_cracencore_s.cryptmstrdma().status().read();
+    status.fetchbusy().bit_is_set() || status.pushbusy().bit_is_set()
+}
+
+fn cryptomaster_fault(cracen: &Cracen) -> bool {
+    let raw = cracen
+        .global_cracencore_s
+        .cryptmstrdma()
+        .intstatraw()
+        .read();
+    raw.fetchererror().bit_is_set() || raw.pushererror().bit_is_set()
+}
+
+// Waits for both directions to finish or fail, bounded by `WAIT_LIMIT`
+fn cryptomaster_wait(cracen: &Cracen) -> Result<(), CracenError> {
+    let waited = wait_until(|| !cryptomaster_busy(cracen) || cryptomaster_fault(cracen));
+    cryptomaster_status(cracen, waited)
+}
+
+// Turns the DMA error bits into a `Result`. After a failure both directions
+// are reset, so no descriptor touches the caller's buffers any more.
+fn cryptomaster_status(
+    cracen: &Cracen,
+    waited: Result<(), CracenError>,
+) -> Result<(), CracenError> {
+    let dma = cracen.global_cracencore_s.cryptmstrdma();
+    let raw = dma.intstatraw().read();
+    let result = if raw.fetchererror().bit_is_set() {
+        Err(CracenError::FetchBusError)
+    } else if raw.pushererror().bit_is_set() {
+        Err(CracenError::PushBusError)
+    } else {
+        waited
+    };
+
+    if result.is_err() {
+        dma.config().write(|w| w.softrst().set_bit());
+        dma.config().write(|w| w.softrst().clear_bit());
+        dma.intstatclr().write(|w| {
+            w.fetchererror().set_bit();
+            w.pushererror().set_bit()
+        });
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_constants() {
+        assert_eq!(TAG_HASH_HEADER, 0x13);
+        assert_eq!(TAG_HASH_DATA, 0x03);
+        assert_eq!(TAG_HASH_DATA_LAST, 0x23);
+        assert_eq!(TAG_HASH_STATE, 0x63);
+        assert_eq!(TAG_SINK, 0x20);
+        assert_eq!(tag_ignore_bytes(3), 0x300);
+        assert_eq!(LAST_DESC_PTR as usize, 1);
+    }
+
+    #[test]
+    fn fetch_sz_realigns_after_last_and_config() {
+        assert_eq!(fetch_sz(63, TAG_HASH_DATA), 63);
+        assert_eq!(fetch_sz(65, TAG_HASH_DATA_LAST), 0x2000_0041);
+        assert_eq!(fetch_sz(4, TAG_HASH_HEADER), 0x2000_0004);
+        assert_eq!(fetch_sz(32, TAG_HASH_STATE), 0x2000_0020);
+        assert_eq!(fetch_sz(0, TAG_ENGINE_BA418), 0);
+    }
+
+    #[test]
+    fn padded_encoding_rounds_up_and_ignores_padding() {
+        assert_eq!(padded_encoding(0, TAG_HASH_DATA_LAST), (0x2000_0004, 0x423));
+        assert_eq!(padded_encoding(1, TAG_HASH_DATA_LAST), (0x2000_0004, 0x323));
+        assert_eq!(padded_encoding(4, TAG_HASH_DATA_LAST), (0x2000_0004, 0x23));
+        assert_eq!(padded_encoding(5, TAG_HASH_DATA_LAST), (0x2000_0008, 0x323));
+        assert_eq!(padded_encoding(64, TAG_SINK), (0x2000_0040, 0x20));
+        assert_eq!(dmatag_for(7), 0x123);
+        assert_eq!(sz(7), 0x2000_0008);
+    }
+
+    fn assert_encodings(descs: &[SxDesc], expected: &[(u32, u32)]) {
+        assert_eq!(descs.len(), expected.len());
+        for (desc, &(sz, dmatag)) in descs.iter().zip(expected) {
+            assert_eq!((desc.sz, desc.dmatag), (sz, dmatag));
+        }
+    }
+
+    #[test]
+    fn hash_update_chain() {
+        let header = [0u8; 4];
+        let state = [0u8; 32];
+        let block = [0u8; 63];
+        let data = [0u8; 65];
+        let mut out = [0u8; 32];
+
+        let mut chain = DescriptorChain::new();
+        chain
+            .push_fetch(&header, TAG_HASH_HEADER)
+            .push_fetch(&state, TAG_HASH_STATE)
+            .push_fetch(&block, TAG_HASH_DATA)
+            .push_fetch(&[], TAG_HASH_DATA)
+            .push_fetch(&data, TAG_HASH_DATA_LAST)
+            .push_sink(&mut out, TAG_SINK);
+
+        assert_encodings(
+            chain.fetch_descriptors(),
+            &[
+                (0x2000_0004, 0x13),
+                (0x2000_0020, 0x63),
+                (63, 0x03),
+                (0x2000_0041, 0x23),
+            ],
+        );
+        assert_encodings(chain.sink_descriptors(), &[(0x2000_0020, 0x20)]);
+        assert_eq!(chain.fetch_descriptors()[2].addr, block.as_ptr() as *mut u8);
+    }
+
+    #[test]
+    fn padded_empty_fetch_reads_zero_word() {
+        let mut chain = DescriptorChain::new();
+        chain.push_fetch_padded(&[], TAG_HASH_DATA_LAST);
+        let desc = chain.fetch_descriptors()[0];
+        assert_eq!((desc.sz, desc.dmatag), (0x2000_0004, 0x423));
+        assert_eq!(desc.addr, ZERO_WORD.as_ptr() as *mut u8);
+    }
+
+    #[test]
+    fn link_chains_descriptors_and_ends_on_sentinel() {
+        let a = [0u8; 8];
+        let b = [0u8; 8];
+        let mut out = [0u8; 8];
+        let mut chain = DescriptorChain::new();
+        chain
+            .push_fetch(&a, TAG_HASH_DATA)
+            .push_fetch(&b, TAG_HASH_DATA_LAST)
+            .push_sink(&mut out, TAG_SINK);
+        chain.link().unwrap();
+
+        let fetch = chain.fetch_descriptors();
+        assert_eq!(fetch[0].next as *const SxDesc, &fetch[1] as *const SxDesc);
+        assert_eq!(fetch[1].next, LAST_DESC_PTR);
+        assert_eq!(chain.sink_descriptors()[0].next, LAST_DESC_PTR);
+    }
+
+    #[test]
+    fn link_rejects_one_sided_chains() {
+        let a = [0u8; 8];
+        let mut chain = DescriptorChain::new();
+        chain.push_fetch(&a, TAG_HASH_DATA_LAST);
+        assert_eq!(chain.link(), Err(CracenError::InvalidInput));
+
+        let mut out = [0u8; 8];
+        let mut chain = DescriptorChain::new();
+        chain.push_sink(&mut out, TAG_SINK);
+        assert_eq!(chain.link(), Err(CracenError::InvalidInput));
+    }
+
+    #[test]
+    #[should_panic(expected = "fetch list full")]
+    fn fetch_list_overflow_panics() {
+        let a = [0u8; 4];
+        let mut chain = DescriptorChain::new();
+        for _ in 0..=MAX_FETCH_DESCRIPTORS {
+            chain.push_fetch(&a, TAG_HASH_DATA);
+        }
+    }
+}
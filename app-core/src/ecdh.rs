@@ -0,0 +1,67 @@
+//! P-256 ECDH with validation of the peer's public key.
+
+use crate::keys::{P256_PRIME, is_zero, less_than};
+use crate::{Cracen, CracenError, PkCommand, PkCurve, PrivateScalar, Slot, wipe};
+
+/// The x-coordinate of an ECDH shared point, wiped on drop.
+///
+/// Feed it to a KDF rather than using it as a key directly.
+pub struct SharedSecret([u8; 32]);
+
+impl SharedSecret {
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        wipe(&mut self.0);
+    }
+}
+
+impl Cracen {
+    /// P-256 ECDH between `priv_key` and the peer's public key.
+    ///
+    /// Unlike [`ec_scalar_mul`](Self::ec_scalar_mul), which multiplies
+    /// whatever point it is given, the peer point is checked first: both
+    /// coordinates must be below p and the point must lie on the curve. That
+    /// rules out invalid-curve attacks, which would otherwise leak `priv_key`
+    /// bit by bit. `priv_key` must be in [1, n-1]. Either check failing
+    /// gives [`CracenError::InvalidInput`].
+    pub fn ecdh_p256(
+        &mut self,
+        priv_key: &impl PrivateScalar<32>,
+        peer_x: &[u8; 32],
+        peer_y: &[u8; 32],
+    ) -> Result<SharedSecret, CracenError> {
+        let priv_key = priv_key.scalar();
+        if is_zero(priv_key) || !less_than(priv_key, PkCurve::P256.order()) {
+            return Err(CracenError::InvalidInput);
+        }
+        if !less_than(peer_x, &P256_PRIME) || !less_than(peer_y, &P256_PRIME) {
+            return Err(CracenError::InvalidInput);
+        }
+
+        let mut job = self.pk_job(PkCommand::EcPointCheck, PkCurve::P256)?;
+        job.write_point(Slot::new(12), peer_x, peer_y)?;
+        match job.run() {
+            // The affine encoding has no point at infinity, so any failure
+            // means the point is not on the curve
+            Err(CracenError::Timeout) => return Err(CracenError::Timeout),
+            Err(_) => return Err(CracenError::InvalidInput),
+            Ok(()) => {}
+        }
+        drop(job);
+
+        let mut x = [0u8; 32];
+        let mut y = [0u8; 32];
+        let result = self.ec_scalar_mul(priv_key, peer_x, peer_y, &mut x, &mut y);
+        wipe(&mut y);
+        if let Err(err) = result {
+            wipe(&mut x);
+            return Err(err);
+        }
+        Ok(SharedSecret(x))
+    }
+}
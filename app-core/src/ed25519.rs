@@ -0,0 +1,316 @@
+//! Ed25519 (RFC 8032) with SHA-512 on the CryptoMaster and the Edwards
+//! arithmetic in the PK engine microcode.
+//!
+//! Scalars, hashes and encoded points are little-endian in RFC 8032 and
+//! big-endian in the PK engine, so every operand is reversed on its way
+//! through; 64-byte hashes take two slots, low half first.
+
+use crate::{Cracen, CracenError, HashAlg, PkCommand, PkCurve, PkJob, Slot, wipe};
+
+/// Group order L = 2^252 + 27742317777372353535851937790883648493,
+/// little-endian.
+const ORDER: [u8; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+];
+
+/// A 32-byte Ed25519 secret key, wiped on drop.
+pub struct Ed25519SecretKey([u8; 32]);
+
+impl Ed25519SecretKey {
+    /// Any 32 bytes are a valid secret key.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
+        Self(*bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl Drop for Ed25519SecretKey {
+    fn drop(&mut self) {
+        wipe(&mut self.0);
+    }
+}
+
+impl Cracen {
+    /// Draws a secret key from the TRNG. Returns `(secret, public)`.
+    pub fn ed25519_keygen(&mut self) -> Result<(Ed25519SecretKey, [u8; 32]), CracenError> {
+        let mut secret = Ed25519SecretKey([0u8; 32]);
+        self.rng(&mut secret.0)?;
+        let public = self.ed25519_public_key(secret.as_bytes())?;
+        Ok((secret, public))
+    }
+
+    /// The public key belonging to the 32-byte `secret`.
+    pub fn ed25519_public_key(&mut self, secret: &[u8; 32]) -> Result<[u8; 32], CracenError> {
+        let mut h = self.ed25519_expand(secret)?;
+        let result = self.ed25519_base_mul(&h[..32]);
+        wipe(&mut h);
+        result
+    }
+
+    /// Signs `msg` with `secret`.
+    ///
+    /// The public key that goes into the signature is derived from `secret`
+    /// rather than passed in, so a mismatched pair can never leak the key.
+    pub fn ed25519_sign(&mut self, secret: &[u8; 32], msg: &[u8]) -> Result<[u8; 64], CracenError> {
+        let mut h = self.ed25519_expand(secret)?;
+        let mut r = [0u8; 64];
+        let result = self.ed25519_sign_expanded(&h, &mut r, msg);
+        wipe(&mut h);
+        wipe(&mut r);
+        result
+    }
+
+    /// Verifies `sig` over `msg` under `public`.
+    ///
+    /// Fails with [`CracenError::InvalidSignature`] if the signature does not
+    /// match, S is not below the group order, or either point does not decode.
+    pub fn ed25519_verify(
+        &mut self,
+        public: &[u8; 32],
+        msg: &[u8],
+        sig: &[u8; 64],
+    ) -> Result<(), CracenError> {
+        let (big_r, s) = sig.split_at(32);
+        if !is_canonical(s) {
+            return Err(CracenError::InvalidSignature);
+        }
+
+        // k = SHA-512(R || A || M)
+        let mut k = [0u8; 64];
+        self.hash_vectored(HashAlg::Sha2_512, &[big_r, public, msg], &mut k)?;
+
+        let mut job = self.pk_job(PkCommand::EddsaVerify, PkCurve::Ed25519)?;
+        write_le(&mut job, Slot::new(8), &k)?;
+        write_le(&mut job, Slot::new(10), public)?;
+        write_le(&mut job, Slot::new(11), s)?;
+        write_le(&mut job, Slot::new(12), big_r)?;
+        match job.run() {
+            Err(
+                CracenError::Pk { .. }
+                | CracenError::NotInvertible
+                | CracenError::PointNotOnCurve
+                | CracenError::PointAtInfinity,
+            ) => Err(CracenError::InvalidSignature),
+            result => result,
+        }
+    }
+
+    fn ed25519_sign_expanded(
+        &mut self,
+        h: &[u8; 64],
+        r: &mut [u8; 64],
+        msg: &[u8],
+    ) -> Result<[u8; 64], CracenError> {
+        let public = self.ed25519_base_mul(&h[..32])?;
+
+        // r = SHA-512(prefix || M), R = [r]B
+        self.hash_vectored(HashAlg::Sha2_512, &[&h[32..], msg], r)?;
+        let big_r = self.ed25519_base_mul(r)?;
+
+        // k = SHA-512(R || A || M)
+        let mut k = [0u8; 64];
+        self.hash_vectored(HashAlg::Sha2_512, &[&big_r, &public, msg], &mut k)?;
+
+        // S = (r + k * s) mod L
+        let mut job = self.pk_job(PkCommand::EddsaSign, PkCurve::Ed25519)?;
+        write_le(&mut job, Slot::new(8), &k)?;
+        write_le(&mut job, Slot::new(10), r)?;
+        write_le(&mut job, Slot::new(12), &h[..32])?;
+        job.run()?;
+
+        let mut sig = [0u8; 64];
+        sig[..32].copy_from_slice(&big_r);
+        job.read(Slot::new(13), &mut sig[32..])?;
+        sig[32..].reverse();
+        Ok(sig)
+    }
+
+    // SHA-512 of the secret key, with the scalar half clamped
+    fn ed25519_expand(&mut self, secret: &[u8; 32]) -> Result<[u8; 64], CracenError> {
+        let mut h = [0u8; 64];
+        self.sha512(secret, &mut h)?;
+        clamp(&mut h);
+        Ok(h)
+    }
+
+    // [scalar]B for a little-endian scalar of up to 64 bytes, encoded
+    fn ed25519_base_mul(&mut self, scalar: &[u8]) -> Result<[u8; 32], CracenError> {
+        let mut wide = [0u8; 64];
+        wide[..scalar.len()].copy_from_slice(scalar);
+
+        let mut job = self.pk_job(PkCommand::EddsaPointMul, PkCurve::Ed25519)?;
+        let result = write_le(&mut job, Slot::new(8), &wide).and_then(|()| job.run());
+        wipe(&mut wide);
+        result?;
+
+        let mut x = [0u8; 32];
+        let mut y = [0u8; 32];
+        job.read_point(Slot::new(10), &mut x, &mut y)?;
+        Ok(encode_point(&x, &y))
+    }
+}
+
+// RFC 8032 5.1.5: the low three bits of the scalar half cleared, bit 254
+// set and bit 255 cleared
+fn clamp(h: &mut [u8; 64]) {
+    h[0] &= 248;
+    h[31] &= 127;
+    h[31] |= 64;
+}
+
+// Encodes an affine point read big-endian from the PK engine: y
+// little-endian, with the sign of x in the top bit
+fn encode_point(x: &[u8; 32], y: &[u8; 32]) -> [u8; 32] {
+    let mut encoded = *y;
+    encoded.reverse();
+    encoded[31] |= (x[31] & 1) << 7;
+    encoded
+}
+
+// Writes a little-endian value into consecutive slots, 32 bytes each, lowest
+// first
+fn write_le(job: &mut PkJob<'_>, mut slot: Slot, value: &[u8]) -> Result<(), CracenError> {
+    for chunk in value.chunks(32) {
+        let mut operand = [0u8; 32];
+        operand.copy_from_slice(chunk);
+        operand.reverse();
+        let result = job.write(slot, &operand);
+        wipe(&mut operand);
+        result?;
+        slot = slot.next();
+    }
+    Ok(())
+}
+
+// S < L, compared from the most significant byte down
+fn is_canonical(s: &[u8]) -> bool {
+    for (a, l) in s.iter().rev().zip(ORDER.iter().rev()) {
+        if a != l {
+            return a < l;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+    use curve25519_dalek::{EdwardsPoint, Scalar};
+    use ed25519_dalek::hazmat::ExpandedSecretKey;
+    use ed25519_dalek::{Signature, Signer, SigningKey, Verifier};
+    use sha2::{Digest, Sha512};
+
+    // RFC 8032 7.1 TEST 1 key over the 300-byte message whose byte i is
+    // i mod 256, which bin/ed25519.rs signs on the device as SIG_LONG
+    const SECRET_1: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+    const SIG_LONG: &str = "b17c42832c88facb5fa322b53293dbef809d7fb0b454175df9d65e9adb965790\
+                            46c75c05e51f32760cec24755c66f393431bab2905a4f387216976ceef57780f";
+
+    // Base point B of RFC 8032 5.1, big-endian as the PK engine returns it,
+    // and the x of -B
+    const BX: &str = "216936d3cd6e53fec0a4e231fdd6dc5c692cc7609525a7b2c9562d608f25d51a";
+    const BY: &str = "6666666666666666666666666666666666666666666666666666666666666658";
+    const NEG_BX: &str = "5e96c92c3291ac013f5b1dce022923a396d3389f6ada584d36a9d29f70da2ad3";
+
+    fn unhex<const N: usize>(s: &str) -> [u8; N] {
+        let mut out = [0u8; N];
+        hex::decode_to_slice(s, &mut out).unwrap();
+        out
+    }
+
+    fn long_msg() -> [u8; 300] {
+        let mut msg = [0u8; 300];
+        for (i, b) in msg.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        msg
+    }
+
+    // S + L, which verifies in the group but is not a canonical encoding
+    fn add_order(sig: &mut [u8; 64]) {
+        let mut carry = 0u16;
+        for (s, l) in sig[32..].iter_mut().zip(ORDER) {
+            let sum = u16::from(*s) + u16::from(l) + carry;
+            *s = sum as u8;
+            carry = sum >> 8;
+        }
+    }
+
+    #[test]
+    fn clamp_matches_dalek() {
+        for secret in [unhex::<32>(SECRET_1), [0; 32], [0xff; 32]] {
+            let mut h: [u8; 64] = Sha512::digest(secret).into();
+            let expanded = ExpandedSecretKey::from_bytes(&h);
+            clamp(&mut h);
+
+            let scalar = Scalar::from_bytes_mod_order(h[..32].try_into().unwrap());
+            assert_eq!(scalar, expanded.scalar);
+            assert_eq!(&h[32..], &expanded.hash_prefix);
+            assert_eq!(
+                EdwardsPoint::mul_base(&scalar).compress().to_bytes(),
+                SigningKey::from_bytes(&secret).verifying_key().to_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn encode_point_matches_dalek() {
+        let by = unhex::<32>(BY);
+        assert_eq!(
+            encode_point(&unhex(BX), &by),
+            ED25519_BASEPOINT_POINT.compress().to_bytes()
+        );
+        assert_eq!(
+            encode_point(&unhex(NEG_BX), &by),
+            (-ED25519_BASEPOINT_POINT).compress().to_bytes()
+        );
+    }
+
+    #[test]
+    fn long_message_matches_dalek() {
+        let key = SigningKey::from_bytes(&unhex(SECRET_1));
+        assert_eq!(key.sign(&long_msg()).to_bytes(), unhex::<64>(SIG_LONG));
+    }
+
+    #[test]
+    fn canonical_s_agrees_with_dalek() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let msg = long_msg();
+        let mut sig = key.sign(&msg).to_bytes();
+        assert!(is_canonical(&sig[32..]));
+        assert!(
+            key.verifying_key()
+                .verify(&msg, &Signature::from_bytes(&sig))
+                .is_ok()
+        );
+
+        add_order(&mut sig);
+        assert!(!is_canonical(&sig[32..]));
+        assert!(
+            key.verifying_key()
+                .verify(&msg, &Signature::from_bytes(&sig))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn is_canonical_bounds() {
+        let mut below = ORDER;
+        below[0] -= 1;
+        assert!(is_canonical(&below));
+        assert!(!is_canonical(&ORDER));
+        assert!(is_canonical(&[0; 32]));
+        assert!(!is_canonical(&[0xff; 32]));
+    }
+
+    #[test]
+    fn secret_key_round_trip() {
+        let key = Ed25519SecretKey::from_bytes(&[0x5a; 32]);
+        assert_eq!(key.as_bytes(), &[0x5a; 32]);
+    }
+}
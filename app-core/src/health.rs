@@ -0,0 +1,309 @@
+//! SP 800-90B online health tests for the CRACEN entropy source.
+//!
+//! Pure bookkeeping over 8-bit noise samples, with no access to the
+//! hardware, so the same code can be fed injected patterns on the host.
+//! [`Cracen::rng`](crate::Cracen::rng) runs every raw noise byte through it,
+//! before the software conditioning.
+//!
+//! The cutoffs assume a min-entropy of H = 1 bit per byte, a conservative
+//! claim; both tests get stricter as H goes up.
+
+/// Repetition count test cutoff, `1 + ceil(20 / H)` for H = 1 and a false
+/// positive rate of 2^-20 (SP 800-90B 4.4.1).
+pub const RCT_CUTOFF: u16 = 21;
+
+/// Adaptive proportion test window for non-binary samples (SP 800-90B 4.4.2).
+pub const APT_WINDOW: u16 = 512;
+
+/// Adaptive proportion test cutoff, `1 + CRITBINOM(512, 2^-H, 1 - 2^-20)`
+/// for H = 1 (SP 800-90B 4.4.2).
+pub const APT_CUTOFF: u16 = 311;
+
+/// Samples run through both tests and thrown away after power-up
+/// (SP 800-90B 4.3).
+pub const STARTUP_SAMPLES: u16 = 1024;
+
+/// The health test that rejected the noise source.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub enum HealthTestFailure {
+    /// The same sample came up [`RCT_CUTOFF`] times in a row
+    RepetitionCount,
+    /// One sample value filled [`APT_CUTOFF`] of an [`APT_WINDOW`]-sample
+    /// window
+    AdaptiveProportion,
+}
+
+/// State of the repetition count and adaptive proportion tests.
+///
+/// A failure is latched: every later [`feed`](Self::feed) reports it again
+/// until [`restart`](Self::restart).
+#[derive(Clone, Debug, defmt::Format)]
+pub struct HealthTests {
+    rct_sample: u8,
+    rct_count: u16,
+    apt_sample: u8,
+    apt_count: u16,
+    apt_seen: u16,
+    startup_left: u16,
+    failure: Option<HealthTestFailure>,
+}
+
+impl Default for HealthTests {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HealthTests {
+    pub const fn new() -> Self {
+        Self {
+            rct_sample: 0,
+            rct_count: 0,
+            apt_sample: 0,
+            apt_count: 0,
+            apt_seen: 0,
+            startup_left: STARTUP_SAMPLES,
+            failure: None,
+        }
+    }
+
+    /// Forgets all history, including a latched failure, and asks for a new
+    /// startup test. Needed whenever the noise source is powered up again.
+    pub fn restart(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Starts the continuous tests over on a new sample stream, e.g. after
+    /// the noise source was powered down and up again, keeping a passed
+    /// startup test. After a failure, or with the startup test unfinished,
+    /// this is a full [`restart`](Self::restart).
+    pub fn resume(&mut self) {
+        let startup_passed = self.failure.is_none() && self.startup_done();
+        self.restart();
+        if startup_passed {
+            self.startup_left = 0;
+        }
+    }
+
+    /// True once [`STARTUP_SAMPLES`] samples have passed since the last
+    /// restart; only samples fed after that may be used as output.
+    pub fn startup_done(&self) -> bool {
+        self.startup_left == 0
+    }
+
+    pub fn failure(&self) -> Option<HealthTestFailure> {
+        self.failure
+    }
+
+    /// Runs one sample through both tests.
+    pub fn feed(&mut self, sample: u8) -> Result<(), HealthTestFailure> {
+        if let Some(failure) = self.failure {
+            return Err(failure);
+        }
+
+        let result = self
+            .repetition_count(sample)
+            .and(self.adaptive_proportion(sample));
+        if let Err(failure) = result {
+            self.failure = Some(failure);
+        } else if self.startup_left > 0 {
+            self.startup_left -= 1;
+        }
+        result
+    }
+
+    /// Runs the four bytes of a FIFO word through both tests, lowest first.
+    pub fn feed_word(&mut self, word: u32) -> Result<(), HealthTestFailure> {
+        word.to_le_bytes()
+            .into_iter()
+            .try_for_each(|b| self.feed(b))
+    }
+
+    fn repetition_count(&mut self, sample: u8) -> Result<(), HealthTestFailure> {
+        if self.rct_count > 0 && sample == self.rct_sample {
+            self.rct_count += 1;
+            if self.rct_count >= RCT_CUTOFF {
+                return Err(HealthTestFailure::RepetitionCount);
+            }
+        } else {
+            self.rct_sample = sample;
+            self.rct_count = 1;
+        }
+        Ok(())
+    }
+
+    // The first sample of each window is the one whose share is counted
+    fn adaptive_proportion(&mut self, sample: u8) -> Result<(), HealthTestFailure> {
+        if self.apt_seen == 0 {
+            self.apt_sample = sample;
+            self.apt_count = 1;
+        } else if sample == self.apt_sample {
+            self.apt_count += 1;
+            if self.apt_count >= APT_CUTOFF {
+                return Err(HealthTestFailure::AdaptiveProportion);
+            }
+        }
+
+        self.apt_seen += 1;
+        if self.apt_seen == APT_WINDOW {
+            self.apt_seen = 0;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Small LCG standing in for a healthy noise source
+    fn noise(state: &mut u32) -> u8 {
+        *state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        (*state >> 16) as u8
+    }
+
+    #[test]
+    fn healthy_samples_finish_startup() {
+        let mut seed = 1;
+        let mut health = HealthTests::new();
+        for _ in 0..STARTUP_SAMPLES {
+            assert!(!health.startup_done());
+            health.feed(noise(&mut seed)).unwrap();
+        }
+        assert!(health.startup_done());
+        for _ in 0..10 * APT_WINDOW {
+            health.feed(noise(&mut seed)).unwrap();
+        }
+    }
+
+    #[test]
+    fn resume_keeps_passed_startup() {
+        let mut seed = 1;
+        let mut health = HealthTests::new();
+        for _ in 0..STARTUP_SAMPLES {
+            health.feed(noise(&mut seed)).unwrap();
+        }
+        for _ in 1..RCT_CUTOFF {
+            health.feed(0xaa).unwrap();
+        }
+        health.resume();
+        assert!(health.startup_done());
+        // The run of 0xaa belonged to the previous stream
+        health.feed(0xaa).unwrap();
+    }
+
+    #[test]
+    fn resume_restarts_unfinished_or_failed_startup() {
+        let mut seed = 1;
+        let mut health = HealthTests::new();
+        health.feed(noise(&mut seed)).unwrap();
+        health.resume();
+        assert!(!health.startup_done());
+
+        for _ in 0..STARTUP_SAMPLES {
+            health.feed(noise(&mut seed)).unwrap();
+        }
+        for _ in 0..RCT_CUTOFF {
+            let _ = health.feed(0xaa);
+        }
+        assert!(health.failure().is_some());
+        health.resume();
+        assert_eq!(health.failure(), None);
+        assert!(!health.startup_done());
+    }
+
+    #[test]
+    fn repetition_count_trips_on_cutoff() {
+        let mut health = HealthTests::new();
+        for _ in 1..RCT_CUTOFF {
+            health.feed(0xaa).unwrap();
+        }
+        assert_eq!(health.feed(0xaa), Err(HealthTestFailure::RepetitionCount));
+    }
+
+    #[test]
+    fn repetition_count_resets_on_change() {
+        let mut health = HealthTests::new();
+        for _ in 0..4 {
+            for _ in 1..RCT_CUTOFF {
+                health.feed(0xaa).unwrap();
+            }
+            health.feed(0x55).unwrap();
+        }
+    }
+
+    // One window with `value` on exactly `count` samples, broken up often
+    // enough that the repetition count test stays quiet
+    fn apt_window(
+        health: &mut HealthTests,
+        value: u8,
+        count: u16,
+    ) -> Result<(), HealthTestFailure> {
+        let mut placed = 0;
+        let mut other = 0u8;
+        for i in 0..APT_WINDOW {
+            let sample = if placed < count && i % 16 != 15 {
+                placed += 1;
+                value
+            } else {
+                other = other.wrapping_add(1) | 0x80;
+                other
+            };
+            health.feed(sample)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn adaptive_proportion_trips_on_cutoff() {
+        let mut health = HealthTests::new();
+        apt_window(&mut health, 0x07, APT_CUTOFF - 1).unwrap();
+        apt_window(&mut health, 0x07, APT_CUTOFF - 1).unwrap();
+        assert_eq!(
+            apt_window(&mut health, 0x07, APT_CUTOFF),
+            Err(HealthTestFailure::AdaptiveProportion)
+        );
+    }
+
+    #[test]
+    fn failures_latch_until_restart() {
+        let mut health = HealthTests::new();
+        for _ in 0..RCT_CUTOFF {
+            let _ = health.feed(0);
+        }
+        assert_eq!(health.failure(), Some(HealthTestFailure::RepetitionCount));
+        assert_eq!(health.feed(0x55), Err(HealthTestFailure::RepetitionCount));
+
+        health.restart();
+        assert_eq!(health.failure(), None);
+        assert!(!health.startup_done());
+        health.feed(0).unwrap();
+    }
+
+    #[test]
+    fn failed_samples_do_not_count_towards_startup() {
+        let mut health = HealthTests::new();
+        for _ in 0..RCT_CUTOFF {
+            let _ = health.feed(0);
+        }
+        assert_eq!(health.startup_left, STARTUP_SAMPLES - (RCT_CUTOFF - 1));
+    }
+
+    #[test]
+    fn feed_word_takes_lowest_byte_first() {
+        let mut health = HealthTests::new();
+        for _ in 2..RCT_CUTOFF {
+            health.feed(0x11).unwrap();
+        }
+        health.feed_word(0x1111_1122).unwrap();
+
+        let mut health = HealthTests::new();
+        for _ in 2..RCT_CUTOFF {
+            health.feed(0x11).unwrap();
+        }
+        assert_eq!(
+            health.feed_word(0x2211_1111),
+            Err(HealthTestFailure::RepetitionCount)
+        );
+    }
+}
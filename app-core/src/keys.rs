@@ -0,0 +1,139 @@
+//! P-256 key pairs generated on the device.
+
+use crate::{Cracen, CracenError, PkCurve, wipe};
+
+/// Field prime p of P-256, big-endian.
+pub(crate) const P256_PRIME: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+];
+
+/// Base point G of P-256.
+const P256_GX: [u8; 32] = [
+    0x6b, 0x17, 0xd1, 0xf2, 0xe1, 0x2c, 0x42, 0x47, 0xf8, 0xbc, 0xe6, 0xe5, 0x63, 0xa4, 0x40, 0xf2,
+    0x77, 0x03, 0x7d, 0x81, 0x2d, 0xeb, 0x33, 0xa0, 0xf4, 0xa1, 0x39, 0x45, 0xd8, 0x98, 0xc2, 0x96,
+];
+const P256_GY: [u8; 32] = [
+    0x4f, 0xe3, 0x42, 0xe2, 0xfe, 0x1a, 0x7f, 0x9b, 0x8e, 0xe7, 0xeb, 0x4a, 0x7c, 0x0f, 0x9e, 0x16,
+    0x2b, 0xce, 0x33, 0x57, 0x6b, 0x31, 0x5e, 0xce, 0xcb, 0xb6, 0x40, 0x68, 0x37, 0xbf, 0x51, 0xf5,
+];
+
+/// A P-256 private scalar in [1, n-1], wiped on drop.
+pub struct PrivateKey([u8; 32]);
+
+impl PrivateKey {
+    /// Takes a big-endian scalar, failing with [`CracenError::InvalidInput`]
+    /// if it is outside [1, n-1].
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, CracenError> {
+        if is_valid_scalar(bytes) {
+            Ok(Self(*bytes))
+        } else {
+            Err(CracenError::InvalidInput)
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        wipe(&mut self.0);
+    }
+}
+
+/// A big-endian private scalar, as taken by the ECDSA signing methods and
+/// [`Cracen::ecdh_p256`]: either a plain `[u8; N]` or a [`PrivateKey`].
+pub trait PrivateScalar<const N: usize> {
+    fn scalar(&self) -> &[u8; N];
+}
+
+impl<const N: usize> PrivateScalar<N> for [u8; N] {
+    fn scalar(&self) -> &[u8; N] {
+        self
+    }
+}
+
+impl PrivateScalar<32> for PrivateKey {
+    fn scalar(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+/// An affine P-256 public key, wiped on drop like the private half.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublicKey {
+    x: [u8; 32],
+    y: [u8; 32],
+}
+
+impl PublicKey {
+    pub fn x(&self) -> &[u8; 32] {
+        &self.x
+    }
+
+    pub fn y(&self) -> &[u8; 32] {
+        &self.y
+    }
+}
+
+impl Drop for PublicKey {
+    fn drop(&mut self) {
+        wipe(&mut self.x);
+        wipe(&mut self.y);
+    }
+}
+
+impl Cracen {
+    /// Generates a P-256 key pair.
+    ///
+    /// The private scalar is drawn from the TRNG and redrawn until it lands
+    /// in [1, n-1] (FIPS 186-5 A.2.2), which takes more than one draw with
+    /// probability below 2^-32. The public key is d * G.
+    pub fn generate_p256_keypair(&mut self) -> Result<(PrivateKey, PublicKey), CracenError> {
+        let mut d = [0u8; 32];
+        loop {
+            self.rng(&mut d)?;
+            if is_valid_scalar(&d) {
+                break;
+            }
+        }
+        let private = PrivateKey(d);
+        wipe(&mut d);
+
+        let mut public = PublicKey {
+            x: [0; 32],
+            y: [0; 32],
+        };
+        self.ec_scalar_mul(
+            private.as_bytes(),
+            &P256_GX,
+            &P256_GY,
+            &mut public.x,
+            &mut public.y,
+        )?;
+        Ok((private, public))
+    }
+}
+
+// 1 <= d < n
+fn is_valid_scalar(d: &[u8; 32]) -> bool {
+    !is_zero(d) & less_than(d, PkCurve::P256.order())
+}
+
+// a < b for big-endian values, without branching on their contents
+pub(crate) fn less_than(a: &[u8], b: &[u8]) -> bool {
+    let mut borrow = 0u16;
+    for (x, y) in a.iter().zip(b).rev() {
+        let diff = u16::from(*x)
+            .wrapping_sub(u16::from(*y))
+            .wrapping_sub(borrow);
+        borrow = (diff >> 8) & 1;
+    }
+    borrow == 1
+}
+
+pub(crate) fn is_zero(a: &[u8]) -> bool {
+    a.iter().fold(0, |acc, b| acc | b) == 0
+}
@@ -1,6 +1,38 @@
 #![no_std]
 
-use defmt::info;
+use core::cell::RefCell;
+
+use digest::consts::{U20, U28, U32, U48, U64, U128};
+use sha2::{Digest, Sha256};
+
+mod cracen;
+mod dma;
+mod drbg;
+mod ecdh;
+mod ed25519;
+mod health;
+mod keys;
+mod noise;
+mod pk;
+mod rfc6979;
+mod rng;
+mod sha3;
+mod shared;
+mod x25519;
+pub use cracen::*;
+pub use dma::*;
+pub use drbg::*;
+pub use ecdh::*;
+pub use ed25519::*;
+pub use health::*;
+pub use keys::*;
+pub use pk::*;
+pub use rfc6979::*;
+pub use rng::*;
+pub use sha3::*;
+pub use shared::*;
+pub use x25519::*;
+
 // Supported hash algorithm bitmasks
 #[repr(u8)]
 #[derive(Copy, Clone, Debug, defmt::Format)]
@@ -10,13 +42,87 @@ pub enum HashAlg {
     Sha2_256 = 0x08,
     Sha2_384 = 0x10,
     Sha2_512 = 0x20,
-    // Sm3 = 0x40,      // TODO: implement
+    Sm3 = 0x40,
 }
 
-#[derive(Debug)]
-pub enum ShaError {
+/// Errors reported by the CRACEN drivers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub enum CracenError {
+    /// The engine is still running a previous operation
     Busy,
+    /// Wrong buffer length, unsupported parameters or malformed data
     InvalidInput,
+    /// A recomputed MAC does not match the expected tag
+    TagMismatch,
+    /// The PK engine rejected an ECDSA signature
+    InvalidSignature,
+    /// A point given to the PK engine is not on the curve
+    PointNotOnCurve,
+    /// A PK operation got or produced the point at infinity
+    PointAtInfinity,
+    /// A PK operation needed the inverse of a value that has none
+    NotInvertible,
+    /// The fetch DMA hit a bus error reading a descriptor or its buffer
+    FetchBusError,
+    /// The push DMA hit a bus error writing a descriptor or its buffer
+    PushBusError,
+    /// The PK engine finished with error flags set; `fail_ptr` is the
+    /// operand slot that caused it
+    Pk { error_flags: u32, fail_ptr: u32 },
+    /// A hardware wait did not finish within [`WAIT_LIMIT`] polls
+    Timeout,
+    /// The entropy source failed an SP 800-90B health test
+    HealthTest(HealthTestFailure),
+    /// A DRBG used up its reseed interval and needs fresh entropy
+    ReseedRequired,
+}
+
+impl core::fmt::Display for CracenError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Busy => f.write_str("engine busy"),
+            Self::InvalidInput => f.write_str("invalid input"),
+            Self::TagMismatch => f.write_str("tag mismatch"),
+            Self::InvalidSignature => f.write_str("invalid signature"),
+            Self::PointNotOnCurve => f.write_str("point not on curve"),
+            Self::PointAtInfinity => f.write_str("point at infinity"),
+            Self::NotInvertible => f.write_str("value not invertible"),
+            Self::FetchBusError => f.write_str("fetch DMA bus error"),
+            Self::PushBusError => f.write_str("push DMA bus error"),
+            Self::Pk {
+                error_flags,
+                fail_ptr,
+            } => write!(
+                f,
+                "PK engine error (flags {error_flags:#x}, slot {fail_ptr})"
+            ),
+            Self::Timeout => f.write_str("hardware timeout"),
+            Self::HealthTest(HealthTestFailure::RepetitionCount) => {
+                f.write_str("entropy source failed the repetition count test")
+            }
+            Self::HealthTest(HealthTestFailure::AdaptiveProportion) => {
+                f.write_str("entropy source failed the adaptive proportion test")
+            }
+            Self::ReseedRequired => f.write_str("DRBG reseed required"),
+        }
+    }
+}
+
+impl core::error::Error for CracenError {}
+
+/// Number of status polls after which a busy engine is reported as
+/// [`CracenError::Timeout`]. Far above the slowest operation (an ECDSA
+/// verify), while still giving up within about a second.
+pub const WAIT_LIMIT: u32 = 10_000_000;
+
+// Polls `done` until it holds, at most `WAIT_LIMIT` times
+pub(crate) fn wait_until(mut done: impl FnMut() -> bool) -> Result<(), CracenError> {
+    for _ in 0..WAIT_LIMIT {
+        if done() {
+            return Ok(());
+        }
+    }
+    Err(CracenError::Timeout)
 }
 
 const fn hash_out_len(algo: HashAlg) -> usize {
@@ -26,481 +132,547 @@ const fn hash_out_len(algo: HashAlg) -> usize {
         HashAlg::Sha2_256 => 32,
         HashAlg::Sha2_384 => 48,
         HashAlg::Sha2_512 => 64,
+        HashAlg::Sm3 => 32,
     }
 }
 
-pub fn cracen_sha1(
-    p: &nrf54l15_app_pac::Peripherals,
-    input: &[u8],
-    out: &mut [u8; 20],
-) -> Result<(), ShaError> {
-    cracen_hash(p, input, out, HashAlg::Sha1)
-}
-
-pub fn cracen_sha224(
-    p: &nrf54l15_app_pac::Peripherals,
-    input: &[u8],
-    out: &mut [u8; 28],
-) -> Result<(), ShaError> {
-    cracen_hash(p, input, out, HashAlg::Sha2_224)
+// Size of one compression-function input block
+const fn hash_block_len(algo: HashAlg) -> usize {
+    match algo {
+        HashAlg::Sha1 | HashAlg::Sha2_224 | HashAlg::Sha2_256 | HashAlg::Sm3 => 64,
+        HashAlg::Sha2_384 | HashAlg::Sha2_512 => 128,
+    }
 }
 
-pub fn cracen_sha256(
-    p: &nrf54l15_app_pac::Peripherals,
-    input: &[u8],
-    out: &mut [u8; 32],
-) -> Result<(), ShaError> {
-    cracen_hash(p, input, out, HashAlg::Sha2_256)
+// Size of the intermediate (chaining) state pushed out by the engine
+const fn hash_state_len(algo: HashAlg) -> usize {
+    match algo {
+        HashAlg::Sha1 => 20,
+        HashAlg::Sha2_224 | HashAlg::Sha2_256 | HashAlg::Sm3 => 32,
+        HashAlg::Sha2_384 | HashAlg::Sha2_512 => 64,
+    }
 }
 
-pub fn cracen_sha384(
-    p: &nrf54l15_app_pac::Peripherals,
-    input: &[u8],
-    out: &mut [u8; 48],
-) -> Result<(), ShaError> {
-    cracen_hash(p, input, out, HashAlg::Sha2_384)
+// Size of the big-endian message length field appended by the padding
+const fn hash_length_field_len(algo: HashAlg) -> usize {
+    match algo {
+        HashAlg::Sha1 | HashAlg::Sha2_224 | HashAlg::Sha2_256 | HashAlg::Sm3 => 8,
+        HashAlg::Sha2_384 | HashAlg::Sha2_512 => 16,
+    }
 }
 
-pub fn cracen_sha512(
-    p: &nrf54l15_app_pac::Peripherals,
-    input: &[u8],
-    out: &mut [u8; 64],
-) -> Result<(), ShaError> {
-    cracen_hash(p, input, out, HashAlg::Sha2_512)
+const MAX_HASH_BLOCK_LEN: usize = 128;
+const MAX_HASH_STATE_LEN: usize = 64;
+const MAX_HASH_OUT_LEN: usize = 64;
+
+/// Size of a serialized [`HashState`], see [`HashState::export`].
+pub const HASH_STATE_EXPORT_LEN: usize = 212;
+const HASH_STATE_EXPORT_VERSION: u8 = 1;
+
+impl TryFrom<u8> for HashAlg {
+    type Error = CracenError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x02 => Ok(HashAlg::Sha1),
+            0x04 => Ok(HashAlg::Sha2_224),
+            0x08 => Ok(HashAlg::Sha2_256),
+            0x10 => Ok(HashAlg::Sha2_384),
+            0x20 => Ok(HashAlg::Sha2_512),
+            0x40 => Ok(HashAlg::Sm3),
+            _ => Err(CracenError::InvalidInput),
+        }
+    }
 }
 
-// fn cracen_hash<const N: usize>(
-//     p: &nrf54l15_app_pac::Peripherals,
-//     input: &[u8],
-//     out: &mut [u8; N],
-//     alg: HashAlg,
-// ) -> Result<(), ShaError> {
-//     if N != hash_out_len(alg) {
-//         return Err(ShaError::InvalidInput);
-//     }
-//     if input.is_empty() {
-//         return Err(ShaError::InvalidInput);
-//     }
-
-//     let dma = p.global_cracencore_s.cryptmstrdma();
-
-//     let out_ptr = out.as_mut_ptr();
-
-//     // 4-byte algorithm header
-//     let mut header = [alg as u8, 0x06, 0x00, 0x00];
-
-//     // Last descriptor (address = 1)
-//     #[allow(
-//         clippy::manual_dangling_ptr,
-//         reason = "nRF54L15 needs this pointer to be on address 1"
-//     )]
-//     let last_desc: *mut SxDesc = 1 as *mut SxDesc;
-
-//     // Output descriptor
-//     let mut out_desc = SxDesc {
-//         addr: out_ptr,
-//         next: last_desc,
-//         sz: sz(N),
-//         dmatag: 32,
-//     };
-
-//     // Middle descriptor (input)
-//     let mut mid_desc = SxDesc {
-//         addr: input.as_ptr() as *mut u8,
-//         next: last_desc,
-//         sz: sz(input.len()),
-//         dmatag: dmatag_for(input.len()),
-//     };
-
-//     // Outer descriptor (input)
-//     let mut in_desc = SxDesc {
-//         addr: header.as_mut_ptr(),
-//         next: &mut mid_desc,
-//         sz: sz(4),
-//         dmatag: 19,
-//     };
-
-//     // Enable cryptomaster
-//     p.global_cracen_s.enable().write(|w| {
-//         w.cryptomaster().set_bit();
-//         w.rng().set_bit();
-//         w.pkeikg().set_bit()
-//     });
-
-//     // Configure DMA source
-//     dma.fetchaddrlsb()
-//         .write(|w| unsafe { w.bits((&mut in_desc) as *mut _ as u32) });
-
-//     // Configure DMA sink
-//     dma.pushaddrlsb()
-//         .write(|w| unsafe { w.bits((&mut out_desc) as *mut _ as u32) });
-
-//     dma.config().write(|w| {
-//         w.fetchctrlindirect().set_bit();
-//         w.pushctrlindirect().set_bit();
-//         w.fetchstop().clear_bit();
-//         w.pushstop().clear_bit();
-//         w.softrst().clear_bit()
-//     });
-
-//     // Start DMA
-//     dma.start().write(|w| {
-//         w.startfetch().set_bit();
-//         w.startpush().set_bit()
-//     });
-
-//     // Wait
-//     while dma.status().read().fetchbusy().bit_is_set() {}
-//     while dma.status().read().pushbusy().bit_is_set() {}
-
-//     Ok(())
-// }
-
-// TODO: Remove magic numbers
-pub fn dmatag_for(input: usize) -> u32 {
-    const TAG_BASE: u32 = 0x23;
-    const TAG_0: u32 = 0x000;
-    const TAG_1: u32 = 0x300;
-    const TAG_2: u32 = 0x200;
-    const TAG_3: u32 = 0x100;
-
-    if input == 0 {
-        return TAG_BASE | 0x400;
+impl Cracen {
+    pub fn sha1(&mut self, input: &[u8], out: &mut [u8; 20]) -> Result<(), CracenError> {
+        self.hash(input, out, HashAlg::Sha1)
     }
 
-    match input % 4 {
-        0 => TAG_BASE | TAG_0, // -> 0x023 = 35
-        1 => TAG_BASE | TAG_1, // -> 0x323 = 803
-        2 => TAG_BASE | TAG_2, // -> 0x223 = 547
-        3 => TAG_BASE | TAG_3, // -> 0x123 = 291
-        _ => panic!("impossible state"),
+    pub fn sha224(&mut self, input: &[u8], out: &mut [u8; 28]) -> Result<(), CracenError> {
+        self.hash(input, out, HashAlg::Sha2_224)
     }
-}
-
-pub fn sz(n: usize) -> u32 {
-    const DMA_REALIGN: usize = 0x2000_0000;
-    let group_end = (n.saturating_sub(1) / 4 + 1) * 4;
-    (group_end | DMA_REALIGN) as u32
-}
-
-// #[repr(C)]
-#[repr(C, align(32))]
-#[derive(Debug, Clone, Copy, defmt::Format)]
-pub struct SxDesc {
-    pub addr: *mut u8,
-    pub next: *mut SxDesc,
-    pub sz: u32,
-    pub dmatag: u32,
-}
 
-// TODO: make this generic over the hash function
-pub fn cracen_hmac_sha256(
-    p: &nrf54l15_app_pac::Peripherals,
-    key: &[u8],
-    message: &[u8],
-    out: &mut [u8; 32],
-) -> Result<(), ShaError> {
-    // ---- 1. Normalize key ----
-    const BLOCK: usize = 64;
-    let mut key_block = [0u8; BLOCK];
-
-    if key.len() > BLOCK {
-        // K = H(K)
-        let mut tmp = [0u8; 32];
-        cracen_sha256(p, key, &mut tmp)?;
-        key_block[..32].copy_from_slice(&tmp);
-    } else {
-        key_block[..key.len()].copy_from_slice(key);
+    pub fn sha256(&mut self, input: &[u8], out: &mut [u8; 32]) -> Result<(), CracenError> {
+        self.hash(input, out, HashAlg::Sha2_256)
     }
 
-    // ---- 2. ipad / opad ----
-    let mut ipad = [0u8; BLOCK];
-    let mut opad = [0u8; BLOCK];
-
-    for i in 0..BLOCK {
-        let b = key_block[i];
-        ipad[i] = b ^ 0x36;
-        opad[i] = b ^ 0x5c;
+    pub fn sha384(&mut self, input: &[u8], out: &mut [u8; 48]) -> Result<(), CracenError> {
+        self.hash(input, out, HashAlg::Sha2_384)
     }
 
-    // ---- 3. inner hash = SHA256(ipad || message) ----
-    let mut inner_buf = [0u8; BLOCK + 1024]; // static buffer, adjust if needed
-    let mut inner_len = 0;
-
-    inner_buf[..BLOCK].copy_from_slice(&ipad);
-    inner_len += BLOCK;
-
-    inner_buf[inner_len..inner_len + message.len()].copy_from_slice(message);
-    inner_len += message.len();
-
-    let mut inner_hash = [0u8; 32];
-    cracen_sha256(p, &inner_buf[..inner_len], &mut inner_hash)?;
-
-    // ---- 4. outer hash = SHA256(opad || inner_hash) ----
-    let mut outer_buf = [0u8; BLOCK + 32];
-    outer_buf[..BLOCK].copy_from_slice(&opad);
-    outer_buf[BLOCK..BLOCK + 32].copy_from_slice(&inner_hash);
+    pub fn sha512(&mut self, input: &[u8], out: &mut [u8; 64]) -> Result<(), CracenError> {
+        self.hash(input, out, HashAlg::Sha2_512)
+    }
 
-    cracen_sha256(p, &outer_buf, out)?;
+    pub fn sm3(&mut self, input: &[u8], out: &mut [u8; 32]) -> Result<(), CracenError> {
+        self.hash(input, out, HashAlg::Sm3)
+    }
+}
 
-    Ok(())
+impl Cracen {
+    pub fn hmac_sha256(
+        &mut self,
+        key: &[u8],
+        message: &[u8],
+        out: &mut [u8; 32],
+    ) -> Result<(), CracenError> {
+        let mut mac = Hmac::new(self, HashAlg::Sha2_256, key)?;
+        mac.update(self, message)?;
+        mac.finalize(self, out)
+    }
 }
 
-pub fn rng(p: &nrf54l15_app_pac::Peripherals, buf: &mut [u8]) {
-    // TODO: this need to be set before everything
-    // p.global_cracen_s.enable().write(|w| w.rng().set_bit());
-    // Consider enforcing this at initialization
-    // so the library cannot be used.
+/// Streaming HMAC (RFC 2104) on top of [`HashState`].
+///
+/// The message is fed through the incremental hash, so there is no limit on
+/// its length. Any [`HashAlg`] can be used; the key block size follows the
+/// algorithm (64 bytes for SHA-1/224/256, 128 bytes for SHA-384/512).
+pub struct Hmac {
+    inner: HashState,
+    opad_key: [u8; MAX_HASH_BLOCK_LEN],
+}
 
-    p.global_cracencore_s
-        .rngcontrol()
-        .control()
-        .write(|w| w.enable().set_bit());
+impl Hmac {
+    pub fn new(cracen: &mut Cracen, alg: HashAlg, key: &[u8]) -> Result<Self, CracenError> {
+        let block_len = hash_block_len(alg);
+
+        // ---- 1. Normalize key ----
+        let mut key_block = [0u8; MAX_HASH_BLOCK_LEN];
+        if key.len() > block_len {
+            // K = H(K)
+            let out_len = hash_out_len(alg);
+            let mut key_hash = HashState::init(alg);
+            key_hash.update(cracen, key)?;
+            key_hash.finalize_slice(cracen, &mut key_block[..out_len])?;
+        } else {
+            key_block[..key.len()].copy_from_slice(key);
+        }
 
-    let mut idx = 0;
+        // ---- 2. ipad / opad ----
+        let mut ipad = [0u8; MAX_HASH_BLOCK_LEN];
+        let mut opad_key = [0u8; MAX_HASH_BLOCK_LEN];
+        for i in 0..block_len {
+            ipad[i] = key_block[i] ^ 0x36;
+            opad_key[i] = key_block[i] ^ 0x5c;
+        }
 
-    while idx < buf.len() {
-        let level = loop {
-            let l = p.global_cracencore_s.rngcontrol().fifolevel().read().bits() as usize;
-            if l > 0 {
-                break l;
-            }
-        };
+        // ---- 3. start inner hash = H(ipad || ...) ----
+        let mut inner = HashState::init(alg);
+        inner.update(cracen, &ipad[..block_len])?;
 
-        for fifo_idx in 0..level {
-            if idx >= buf.len() {
-                break;
-            }
+        Ok(Self { inner, opad_key })
+    }
 
-            let rnd = p
-                .global_cracencore_s
-                .rngcontrol()
-                .fifo(fifo_idx)
-                .read()
-                .bits();
-            let bytes = rnd.to_le_bytes();
+    pub fn algorithm(&self) -> HashAlg {
+        self.inner.algorithm()
+    }
 
-            let remaining = buf.len() - idx;
-            let take = remaining.min(4);
+    pub fn update(&mut self, cracen: &mut Cracen, data: &[u8]) -> Result<(), CracenError> {
+        self.inner.update(cracen, data)
+    }
 
-            buf[idx..idx + take].copy_from_slice(&bytes[..take]);
-            idx += take;
+    /// Writes the tag into `out`, which must be exactly
+    /// `hash_out_len(algorithm)` bytes long.
+    pub fn finalize<const N: usize>(
+        self,
+        cracen: &mut Cracen,
+        out: &mut [u8; N],
+    ) -> Result<(), CracenError> {
+        self.finalize_slice(cracen, out)
+    }
 
-            if idx >= buf.len() {
-                break;
-            }
+    /// Recomputes the tag and compares it with `tag` in constant time.
+    pub fn verify(self, cracen: &mut Cracen, tag: &[u8]) -> Result<(), CracenError> {
+        let mut expected = [0u8; MAX_HASH_OUT_LEN];
+        let out_len = hash_out_len(self.algorithm());
+        if tag.len() != out_len {
+            return Err(CracenError::InvalidInput);
+        }
+        self.finalize_slice(cracen, &mut expected[..out_len])?;
+
+        let diff = expected[..out_len]
+            .iter()
+            .zip(tag)
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        if diff == 0 {
+            Ok(())
+        } else {
+            Err(CracenError::TagMismatch)
         }
     }
-}
 
-pub unsafe fn write_block<const N: usize>(addr: u32, data: &[u8; N]) {
-    let mut p = addr as *mut u32;
-    for chunk in data.chunks_exact(4) {
-        let v = u32::from_le_bytes(chunk.try_into().unwrap());
-        unsafe { core::ptr::write_volatile(p, v) }
-        p = unsafe { p.add(1) };
-    }
-}
+    fn finalize_slice(mut self, cracen: &mut Cracen, out: &mut [u8]) -> Result<(), CracenError> {
+        let alg = self.algorithm();
+        let block_len = hash_block_len(alg);
+        let out_len = hash_out_len(alg);
 
-pub unsafe fn read32_bytes(addr: u32) -> [u8; 32] {
-    let mut out = [0u8; 32];
-    let mut p: *const u32 = addr as *const u32;
-    for i in 0..8 {
-        let v = unsafe { core::ptr::read_volatile(p) };
-        let bytes = v.to_le_bytes();
-        out[i * 4..i * 4 + 4].copy_from_slice(&bytes);
-        p = unsafe { p.add(1) }
-    }
+        let mut inner_hash = [0u8; MAX_HASH_OUT_LEN];
+        self.inner
+            .finalize_slice(cracen, &mut inner_hash[..out_len])?;
 
-    out
+        // ---- 4. outer hash = H(opad || inner_hash) ----
+        let mut outer = HashState::init(alg);
+        outer.update(cracen, &self.opad_key[..block_len])?;
+        outer.update(cracen, &inner_hash[..out_len])?;
+        outer.finalize_slice(cracen, out)
+    }
 }
 
-pub fn cracen_ecdsa_sign(
-    p: &nrf54l15_app_pac::Peripherals,
-    msg: &[u8],
-    priv_key: &[u8; 32],
-) -> Result<([u8; 32], [u8; 32]), ()> {
-    let cracen = &p.global_cracencore_s;
-
-    // 1. Hash message with SHA-256
-    let mut sha256 = [0u8; 32];
-    cracen_sha256(p, msg, &mut sha256).map_err(|_| ())?;
-
-    // 2. Get 32 bytes of randomness (nonce k)
-    let mut random = [0u8; 32];
-    rng(p, &mut random);
-
-    unsafe {
-        // 4. Configure PK for ECDSA-P256 sign
-        cracen.pk().command().write(|w| {
-            w.opeaddr().bits(0x30); // ECDSA sign micro-op
-            w.opbytesm1().bits(0b0000011111); // 32 bytes - 1
-            w.selcurve().p256(); // P-256 curve
-            w.swapbytes().set_bit()
+/// Raw noise bytes conditioned into each 32-byte block of
+/// [`Cracen::rng`] output. At the H = 1 bit per byte the health tests
+/// assume, that is the 256 + 64 bits of input entropy SP 800-90B 3.1.5.1.2
+/// asks for to claim full entropy from SHA-256.
+pub const RNG_RAW_BYTES_PER_BLOCK: usize = 320;
+
+impl Cracen {
+    /// Fills `buf` with conditioned TRNG output. Fails with
+    /// [`CracenError::Timeout`] if the FIFO stays empty.
+    ///
+    /// The hardware conditioning is bypassed: the raw noise samples go
+    /// through the SP 800-90B health tests, and are then conditioned in
+    /// software, [`RNG_RAW_BYTES_PER_BLOCK`] bytes into each 32 output bytes
+    /// with SHA-256. That costs 80 FIFO words and a software hash per 32
+    /// output bytes, roughly ten times slower than the hardware conditioner.
+    ///
+    /// The first [`STARTUP_SAMPLES`] after boot are only tested, not used.
+    /// The result is kept across power cycles, so later calls go straight
+    /// to the continuous tests. Once a test fails, this returns
+    /// [`CracenError::HealthTest`] and hands out nothing more until the
+    /// noise source has been powered down and has passed the startup test
+    /// again. On its own the RNG is only powered during the call, so a
+    /// failure clears on the next one. A [`CracenRng`] or [`SharedCracen`]
+    /// keeps it powered while it exists, and a failure sticks until that is
+    /// dropped.
+    pub fn rng(&mut self, buf: &mut [u8]) -> Result<(), CracenError> {
+        let _power = self.power(Engine::Rng)?;
+        let rng = self.global_cracencore_s.rngcontrol();
+        rng.control().write(|w| {
+            w.condbypass().set_bit();
+            w.enable().set_bit()
         });
+        // Words queued before the switch may not be raw samples
+        for i in 0..rng.fifolevel().read().bits() as usize {
+            rng.fifo(i).read();
+        }
 
-        // Wait again for command to settle
-        while cracen.pk().status().read().pkbusy().bit_is_set() {}
-        while cracen.ikg().status().read().ctrdrbgbusy().bit_is_set() {}
-
-        // These slot numbers must match what the microcode expects.
-        // Here I keep your mapping:
-        write_block::<32>(slot_addr(6), &priv_key); // private key
-        write_block::<32>(slot_addr(7), &random); // nonce k
-        write_block::<32>(slot_addr(12), &sha256); // hash H(m)
-
-        // 6. Configure PK pointers.
-        cracen.pk().pointers().write(|w| {
-            w.opptra().bits(0); // depends on microcode; keep your values
-            w.opptrb().bits(8);
-            w.opptrc().bits(10)
-        });
+        let mut health = take_health_tests();
+        let result = self.rng_tested(&mut health, buf);
+        critical_section::with(|cs| HEALTH.borrow_ref_mut(cs).0 = health);
 
-        // 7. Start operation
-        cracen.pk().control().write(|w| {
-            w.start().set_bit();
-            w.clearirq().set_bit()
-        });
+        // Back to conditioned output, which the PK engine's IKG seeds from
+        self.global_cracencore_s
+            .rngcontrol()
+            .control()
+            .write(|w| w.enable().set_bit());
+        result
     }
 
-    // 8. Wait for completion
-    while cracen.pk().status().read().pkbusy().bit_is_set() {}
-    while cracen.ikg().status().read().ctrdrbgbusy().bit_is_set() {}
+    fn rng_tested(&mut self, health: &mut HealthTests, buf: &mut [u8]) -> Result<(), CracenError> {
+        if let Some(failure) = health.failure() {
+            return Err(CracenError::HealthTest(failure));
+        }
+
+        let rng = self.global_cracencore_s.rngcontrol();
+        for chunk in buf.chunks_mut(32) {
+            let mut conditioner = Sha256::new();
+            let mut raw = 0;
+
+            while raw < RNG_RAW_BYTES_PER_BLOCK {
+                let mut level = 0;
+                wait_until(|| {
+                    level = rng.fifolevel().read().bits() as usize;
+                    level > 0
+                })?;
+
+                for fifo_idx in 0..level {
+                    if raw >= RNG_RAW_BYTES_PER_BLOCK {
+                        break;
+                    }
+                    let word = rng.fifo(fifo_idx).read().bits();
+
+                    // Words from the startup test are thrown away
+                    let use_word = health.startup_done();
+                    health.feed_word(word).map_err(CracenError::HealthTest)?;
+                    if use_word {
+                        conditioner.update(word.to_le_bytes());
+                        raw += 4;
+                    }
+                }
+            }
 
-    // 9. Read signature r,s from output slots (here: 10 and 11)
-    let bytes_r = unsafe { read32_bytes(slot_addr(10)) };
-    let bytes_s = unsafe { read32_bytes(slot_addr(11)) };
+            let mut block: [u8; 32] = conditioner.finalize().into();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+            wipe(&mut block);
+        }
 
-    Ok((bytes_r, bytes_s))
+        Ok(())
+    }
 }
 
-/// Verify an ECDSA-P256 signature using CRACEN.
-/// - `message`  → the message to hash (SHA-256 is computed inside)
-/// - `sig_r`    → 32-byte R component
-/// - `sig_s`    → 32-byte S component
-/// - `pk_x`     → public key X coordinate
-/// - `pk_y`     → public key Y coordinate
-pub fn cracen_ecdsa_verify(
-    p: &nrf54l15_app_pac::Peripherals,
-    message: &[u8],
-    sig_r: &[u8; 32],
-    sig_s: &[u8; 32],
-    pk_x: &[u8; 32],
-    pk_y: &[u8; 32],
-) -> bool {
-    let cracen = &p.global_cracencore_s;
-
-    // --- 1. Compute SHA-256 of the message ---
-    let mut hash = [0u8; 32];
-    cracen_sha256(p, message, &mut hash).unwrap();
-
-    // --- 2. Wait until CRACEN is idle ---
-    while cracen.pk().status().read().pkbusy().bit_is_set() {}
-    while cracen.ikg().status().read().ctrdrbgbusy().bit_is_set() {}
-
-    unsafe {
-        // --- 3. Configure PK engine for ECDSA verify ---
-        cracen.pk().command().write(|w| {
-            w.opeaddr().bits(0x31); // ECDSA verify
-            w.opbytesm1().bits(0b0000011111); // 32 bytes - 1
-            w.selcurve().p256();
-            w.swapbytes().set_bit()
-        });
+// Health test state, kept across calls so the tests are continuous. Only one
+// `rng` call runs at a time, so it is taken out for the duration of a call.
+static HEALTH: critical_section::Mutex<RefCell<(HealthTests, u32)>> =
+    critical_section::Mutex::new(RefCell::new((HealthTests::new(), 0)));
+
+// Resumes the tests on a new sample stream if the noise source has been
+// powered up since they last ran. A passed startup test is kept, so it only
+// runs again after a failure or `restart_health_tests`.
+fn take_health_tests() -> HealthTests {
+    critical_section::with(|cs| {
+        let (health, cycle) = &mut *HEALTH.borrow_ref_mut(cs);
+        if *cycle != rng_power_cycles() {
+            *cycle = rng_power_cycles();
+            health.resume();
+        }
+        health.clone()
+    })
+}
 
-        while cracen.pk().status().read().pkbusy().bit_is_set() {}
-        while cracen.ikg().status().read().ctrdrbgbusy().bit_is_set() {}
-
-        // --- 4. Load operands into crypto RAM ---
-        write_block::<32>(slot_addr(8), pk_x); // P.x
-        write_block::<32>(slot_addr(9), pk_y); // P.y
-        write_block::<32>(slot_addr(10), sig_r); // R component
-        write_block::<32>(slot_addr(11), sig_s); // S component
-        write_block::<32>(slot_addr(12), &hash); // message hash
-
-        // --- 5. Set pointer table ---
-        cracen.pk().pointers().write(|w| {
-            w.opptra().bits(0);
-            w.opptrb().bits(8); // input A (pubkey)
-            w.opptrc().bits(10) // input B (signature R/S + hash)
-        });
+// Asks for a new startup test, for when the noise source has been run in
+// another mode
+pub(crate) fn restart_health_tests() {
+    critical_section::with(|cs| HEALTH.borrow_ref_mut(cs).0.restart());
+}
 
-        // --- 6. Start operation ---
-        cracen.pk().control().write(|w| {
-            w.start().set_bit();
-            w.clearirq().set_bit()
-        });
+impl Cracen {
+    /// Signs `msg` with ECDSA. The curve follows from the key size: 32 bytes
+    /// for P-256, 48 for P-384 and 66 for P-521, hashed with SHA-256,
+    /// SHA-384 and SHA-512 respectively. Returns `(r, s)`.
+    ///
+    /// The nonce comes straight from the TRNG; see
+    /// [`ecdsa_sign_with_nonce`](Self::ecdsa_sign_with_nonce) for the
+    /// alternatives.
+    pub fn ecdsa_sign<const N: usize>(
+        &mut self,
+        msg: &[u8],
+        priv_key: &impl PrivateScalar<N>,
+    ) -> Result<([u8; N], [u8; N]), CracenError> {
+        self.ecdsa_sign_with_nonce(msg, priv_key, NonceMode::Random)
     }
 
-    // --- 7. Wait for completion ---
-    while cracen.pk().status().read().pkbusy().bit_is_set() {}
-    while cracen.ikg().status().read().ctrdrbgbusy().bit_is_set() {}
-
-    // --- 8. Check result ---
-    let errors = cracen.pk().status().read().errorflags().bits();
-    let failptr = cracen.pk().status().read().failptr().bits();
+    /// Same as [`ecdsa_sign`](Self::ecdsa_sign), with the nonce picked as
+    /// `nonce` says. [`NonceMode::Deterministic`] gives the RFC 6979
+    /// signatures.
+    pub fn ecdsa_sign_with_nonce<const N: usize>(
+        &mut self,
+        msg: &[u8],
+        priv_key: &impl PrivateScalar<N>,
+        nonce: NonceMode,
+    ) -> Result<([u8; N], [u8; N]), CracenError> {
+        let curve = PkCurve::with_op_size(N).ok_or(CracenError::InvalidInput)?;
+        self.ecdsa_sign_with(curve.hash_alg(), msg, priv_key, nonce)
+    }
 
-    errors == 0 && failptr == 0
-}
+    /// Same as [`ecdsa_sign_with_nonce`](Self::ecdsa_sign_with_nonce), with
+    /// `msg` hashed with `alg` instead of the curve's own hash. A digest
+    /// longer than the group order is cut down to its leftmost bits as
+    /// FIPS 186-5 6.4.1 asks, so e.g. SHA-512 works on P-256.
+    pub fn ecdsa_sign_with<const N: usize>(
+        &mut self,
+        alg: HashAlg,
+        msg: &[u8],
+        priv_key: &impl PrivateScalar<N>,
+        nonce: NonceMode,
+    ) -> Result<([u8; N], [u8; N]), CracenError> {
+        let curve = PkCurve::with_op_size(N).ok_or(CracenError::InvalidInput)?;
+
+        // 1. Hash the message
+        let mut digest = [0u8; 64];
+        let digest = self.ecdsa_hash(alg, msg, &mut digest)?;
+
+        self.ecdsa_sign_hashed(curve, alg, digest, priv_key.scalar(), nonce)
+    }
 
-pub fn cracen_ec_scalar_mul(
-    p: &nrf54l15_app_pac::Peripherals,
-    k: &[u8; 32],
-    px: &[u8; 32],
-    py: &[u8; 32],
-    out_x: &mut [u8; 32],
-    out_y: &mut [u8; 32],
-) {
-    let cracen = &p.global_cracencore_s;
-
-    // wait until hardware is idle
-    while cracen.pk().status().read().pkbusy().bit_is_set() {}
-    while cracen.ikg().status().read().ctrdrbgbusy().bit_is_set() {}
-
-    unsafe {
-        // --- Configure PK operation for EC point multiply ---
-        cracen.pk().command().write(|w| {
-            w.opeaddr().bits(0x22); // ECC point multiplication
-            w.opbytesm1().bits(0b0000011111); // 32 bytes - 1
-            w.selcurve().p256(); // P-256 curve
-            w.swapbytes().set_bit() // byte-swap active
-        });
+    /// Signs a digest the caller computed, e.g. with [`HashState`] over an
+    /// image too large for one buffer.
+    ///
+    /// `digest` must be as long as a SHA-1 or SHA-2 output (20, 28, 32, 48
+    /// or 64 bytes), else this fails with [`CracenError::InvalidInput`]. In
+    /// the RFC 6979 modes the HMAC runs over the SHA-2 hash of that length,
+    /// which is what the RFC asks for when `digest` came from that hash. It
+    /// is truncated as in [`ecdsa_sign_with`](Self::ecdsa_sign_with).
+    pub fn ecdsa_sign_prehash<const N: usize>(
+        &mut self,
+        digest: &[u8],
+        priv_key: &impl PrivateScalar<N>,
+        nonce: NonceMode,
+    ) -> Result<([u8; N], [u8; N]), CracenError> {
+        let curve = PkCurve::with_op_size(N).ok_or(CracenError::InvalidInput)?;
+        let alg = prehash_alg(digest)?;
+        self.ecdsa_sign_hashed(curve, alg, digest, priv_key.scalar(), nonce)
+    }
 
-        while cracen.pk().status().read().pkbusy().bit_is_set() {}
-        while cracen.ikg().status().read().ctrdrbgbusy().bit_is_set() {}
+    // 2. Pick the nonce k and sign
+    fn ecdsa_sign_hashed<const N: usize>(
+        &mut self,
+        curve: PkCurve,
+        alg: HashAlg,
+        digest: &[u8],
+        priv_key: &[u8; N],
+        nonce: NonceMode,
+    ) -> Result<([u8; N], [u8; N]), CracenError> {
+        let mut k = self.ecdsa_nonce(nonce, curve, alg, priv_key, digest)?;
+
+        let result = self.ecdsa_sign_digest(curve, digest, &k, priv_key);
+        wipe(&mut k);
+        result
+    }
 
-        // --- Load operands into CRACEN RAM ---
-        // scalar k → slot 8
-        write_block::<32>(slot_addr(8), k);
+    // Signing steps that only need the PK engine
+    pub(crate) fn ecdsa_sign_digest<const N: usize>(
+        &mut self,
+        curve: PkCurve,
+        digest: &[u8],
+        random: &[u8; N],
+        priv_key: &[u8; N],
+    ) -> Result<([u8; N], [u8; N]), CracenError> {
+        let mut job = self.pk_job(PkCommand::EcdsaSign, curve)?;
+        job.write(Slot::new(6), priv_key)?;
+        job.write(Slot::new(7), random)?;
+        job.write(Slot::new(12), &bits2int::<N>(curve, digest))?;
+        job.run()?;
+
+        let mut r = [0u8; N];
+        let mut s = [0u8; N];
+        job.read(Slot::new(10), &mut r)?;
+        job.read(Slot::new(11), &mut s)?;
+        Ok((r, s))
+    }
 
-        // Px → slot 12
-        write_block::<32>(slot_addr(12), px);
+    /// Verify an ECDSA signature using CRACEN, on the curve given by the
+    /// operand size as for [`ecdsa_sign`](Self::ecdsa_sign).
+    /// - `message`  → the message to hash (the hash is computed inside)
+    /// - `sig_r`    → R component
+    /// - `sig_s`    → S component
+    /// - `pk_x`     → public key X coordinate
+    /// - `pk_y`     → public key Y coordinate
+    ///
+    /// Fails with [`CracenError::InvalidSignature`] if the signature does not
+    /// match.
+    pub fn ecdsa_verify<const N: usize>(
+        &mut self,
+        message: &[u8],
+        sig_r: &[u8; N],
+        sig_s: &[u8; N],
+        pk_x: &[u8; N],
+        pk_y: &[u8; N],
+    ) -> Result<(), CracenError> {
+        let curve = PkCurve::with_op_size(N).ok_or(CracenError::InvalidInput)?;
+        self.ecdsa_verify_with(curve.hash_alg(), message, sig_r, sig_s, pk_x, pk_y)
+    }
 
-        // Py → slot 13
-        write_block::<32>(slot_addr(13), py);
+    /// Same as [`ecdsa_verify`](Self::ecdsa_verify), with `message` hashed
+    /// with `alg` as in [`ecdsa_sign_with`](Self::ecdsa_sign_with).
+    pub fn ecdsa_verify_with<const N: usize>(
+        &mut self,
+        alg: HashAlg,
+        message: &[u8],
+        sig_r: &[u8; N],
+        sig_s: &[u8; N],
+        pk_x: &[u8; N],
+        pk_y: &[u8; N],
+    ) -> Result<(), CracenError> {
+        let curve = PkCurve::with_op_size(N).ok_or(CracenError::InvalidInput)?;
+
+        // --- 1. Hash the message ---
+        let mut digest = [0u8; 64];
+        let digest = self.ecdsa_hash(alg, message, &mut digest)?;
+
+        self.ecdsa_verify_digest(curve, digest, sig_r, sig_s, pk_x, pk_y)
+    }
 
-        cracen.pk().pointers().write(|w| {
-            w.opptra().bits(12); // A = point.x (12) and point.y (13)
-            w.opptrb().bits(8); // B = scalar k
-            w.opptrc().bits(10) // C = output base (X=10, Y=11)
-        });
+    /// Verifies a signature over a digest the caller computed, the
+    /// counterpart of [`ecdsa_sign_prehash`](Self::ecdsa_sign_prehash) with
+    /// the same rules for `digest`.
+    pub fn ecdsa_verify_prehash<const N: usize>(
+        &mut self,
+        digest: &[u8],
+        sig_r: &[u8; N],
+        sig_s: &[u8; N],
+        pk_x: &[u8; N],
+        pk_y: &[u8; N],
+    ) -> Result<(), CracenError> {
+        let curve = PkCurve::with_op_size(N).ok_or(CracenError::InvalidInput)?;
+        prehash_alg(digest)?;
+        self.ecdsa_verify_digest(curve, digest, sig_r, sig_s, pk_x, pk_y)
+    }
 
-        // --- Start operation ---
-        cracen.pk().control().write(|w| {
-            w.start().set_bit();
-            w.clearirq().set_bit()
-        });
+    // Verification steps that only need the PK engine
+    pub(crate) fn ecdsa_verify_digest<const N: usize>(
+        &mut self,
+        curve: PkCurve,
+        digest: &[u8],
+        sig_r: &[u8; N],
+        sig_s: &[u8; N],
+        pk_x: &[u8; N],
+        pk_y: &[u8; N],
+    ) -> Result<(), CracenError> {
+        let mut job = self.pk_job(PkCommand::EcdsaVerify, curve)?;
+        job.write_point(Slot::new(8), pk_x, pk_y)?;
+        job.write_point(Slot::new(10), sig_r, sig_s)?;
+        job.write(Slot::new(12), &bits2int::<N>(curve, digest))?;
+
+        // Out-of-range r or s trip other error flags than a plain mismatch
+        match job.run() {
+            Err(CracenError::Pk { .. } | CracenError::NotInvertible) => {
+                Err(CracenError::InvalidSignature)
+            }
+            result => result,
+        }
     }
 
-    // wait for completion
-    while cracen.pk().status().read().pkbusy().bit_is_set() {}
-    while cracen.ikg().status().read().ctrdrbgbusy().bit_is_set() {}
+    /// Computes `k * P`, on the curve given by the operand size as for
+    /// [`ecdsa_sign`](Self::ecdsa_sign).
+    ///
+    /// `P` is used as given, without checking that it is on the curve. For
+    /// key agreement with a peer's key use [`ecdh_p256`](Self::ecdh_p256).
+    pub fn ec_scalar_mul<const N: usize>(
+        &mut self,
+        k: &[u8; N],
+        px: &[u8; N],
+        py: &[u8; N],
+        out_x: &mut [u8; N],
+        out_y: &mut [u8; N],
+    ) -> Result<(), CracenError> {
+        let curve = PkCurve::with_op_size(N).ok_or(CracenError::InvalidInput)?;
+        let mut job = self.pk_job(PkCommand::EcPointMul, curve)?;
+        job.write(Slot::new(8), k)?;
+        job.write_point(Slot::new(12), px, py)?;
+        job.run()?;
+        job.read_point(Slot::new(10), out_x, out_y)
+    }
 
-    // --- Read result back into buffers ---
-    let rx = unsafe { read32_bytes(slot_addr(10)) };
-    let ry = unsafe { read32_bytes(slot_addr(11)) };
+    // Hashes `msg` with `alg` into the front of `digest`
+    fn ecdsa_hash<'d>(
+        &mut self,
+        alg: HashAlg,
+        msg: &[u8],
+        digest: &'d mut [u8; 64],
+    ) -> Result<&'d [u8], CracenError> {
+        match hash_out_len(alg) {
+            20 => self.hash(msg, digest.first_chunk_mut::<20>().unwrap(), alg)?,
+            28 => self.hash(msg, digest.first_chunk_mut::<28>().unwrap(), alg)?,
+            32 => self.hash(msg, digest.first_chunk_mut::<32>().unwrap(), alg)?,
+            48 => self.hash(msg, digest.first_chunk_mut::<48>().unwrap(), alg)?,
+            _ => self.hash(msg, digest, alg)?,
+        }
+        Ok(&digest[..hash_out_len(alg)])
+    }
+}
 
-    out_x.copy_from_slice(&rx);
-    out_y.copy_from_slice(&ry);
+// The SHA-2 (or SHA-1) hash a caller-supplied digest is taken to come from,
+// by its length
+pub(crate) fn prehash_alg(digest: &[u8]) -> Result<HashAlg, CracenError> {
+    match digest.len() {
+        20 => Ok(HashAlg::Sha1),
+        28 => Ok(HashAlg::Sha2_224),
+        32 => Ok(HashAlg::Sha2_256),
+        48 => Ok(HashAlg::Sha2_384),
+        64 => Ok(HashAlg::Sha2_512),
+        _ => Err(CracenError::InvalidInput),
+    }
 }
 
 // Log generated by sdk-nrf:
@@ -514,11 +686,6 @@ pub unsafe fn load_microcode() {
     }
 }
 
-#[inline(always)]
-pub fn slot_addr(slot: u32) -> u32 {
-    0x5180_8000 + slot * 0x200 + 0x1E0
-}
-
 // subsys/nrf_security/src/drivers/cracen/cracenpsa/src/microcode_binary.h
 const MICROCODE: [u32; 1223] = [
     0x408A3800, 0x4094408F, 0x40A640A1, 0x481540AC, 0x40AE40CB, 0x380040A3, 0x38003800, 0x40CE3800,
@@ -676,104 +843,106 @@ const MICROCODE: [u32; 1223] = [
     0x59871002, 0x2CA0A2A0, 0x59891015, 0xA2801A00, 0xA2902C80, 0x1A002C90, 0x5FD61EFA,
 ];
 
-fn cracen_hash<const N: usize>(
-    p: &nrf54l15_app_pac::Peripherals,
-    input: &[u8],
-    out: &mut [u8; N],
-    alg: HashAlg,
-) -> Result<(), ShaError> {
-    if N != hash_out_len(alg) {
-        return Err(ShaError::InvalidInput);
+/// Maximum number of input slices accepted by [`Cracen::hash_vectored`].
+pub const MAX_HASH_FRAGMENTS: usize = 8;
+
+impl Cracen {
+    pub fn hash<const N: usize>(
+        &mut self,
+        input: &[u8],
+        out: &mut [u8; N],
+        alg: HashAlg,
+    ) -> Result<(), CracenError> {
+        self.hash_vectored(alg, &[input], out)
     }
 
-    let dma = p.global_cracencore_s.cryptmstrdma();
-
-    let out_ptr = out.as_mut_ptr();
-
-    // 4-byte algorithm header
-    let mut header = [alg as u8, 0x06, 0x00, 0x00];
+    /// Same as [`hash`](Self::hash), but sleeps until the CryptoMaster
+    /// interrupt signals completion instead of polling. Needs
+    /// [`cracen_enable_interrupt`].
+    pub async fn hash_async<const N: usize>(
+        &mut self,
+        input: &[u8],
+        out: &mut [u8; N],
+        alg: HashAlg,
+    ) -> Result<(), CracenError> {
+        self.hash_vectored_async(alg, &[input], out).await
+    }
 
-    // Output descriptor
-    let mut out_desc = SxDesc {
-        addr: out_ptr,
-        next: LAST_DESC_PTR,
-        sz: sz(N),
-        dmatag: 32,
-    };
+    /// One-shot hash over the concatenation of `inputs` without copying them.
+    ///
+    /// Every non-empty slice gets its own fetch descriptor, so e.g. a packet
+    /// header, payload and trailer living in different buffers can be hashed in
+    /// place. Slices whose length is not a multiple of 4 are realigned by the DMA.
+    pub fn hash_vectored<const N: usize>(
+        &mut self,
+        alg: HashAlg,
+        inputs: &[&[u8]],
+        out: &mut [u8; N],
+    ) -> Result<(), CracenError> {
+        // 4-byte algorithm header
+        let header = [alg as u8, 0x06, 0x00, 0x00];
+        hash_chain(alg, &header, inputs, out)?.run(self)
+    }
 
-    // Middle descriptor (input)
-    let mut mid_desc = SxDesc {
-        addr: input.as_ptr() as *mut u8,
-        next: LAST_DESC_PTR,
-        sz: sz(input.len()),
-        dmatag: dmatag_for(input.len()),
-    };
+    /// Async version of [`hash_vectored`](Self::hash_vectored).
+    pub async fn hash_vectored_async<const N: usize>(
+        &mut self,
+        alg: HashAlg,
+        inputs: &[&[u8]],
+        out: &mut [u8; N],
+    ) -> Result<(), CracenError> {
+        let header = [alg as u8, 0x06, 0x00, 0x00];
+        hash_chain(alg, &header, inputs, out)?.run_async(self).await
+    }
+}
 
-    // Outer descriptor (input)
-    let mut in_desc = SxDesc {
-        addr: header.as_mut_ptr(),
-        next: &mut mid_desc,
-        sz: sz(4),
-        dmatag: 19,
-    };
+// Validates a one-shot request and builds its chain behind `header`
+fn hash_chain<'a, const N: usize>(
+    alg: HashAlg,
+    header: &'a [u8; 4],
+    inputs: &'a [&'a [u8]],
+    out: &'a mut [u8; N],
+) -> Result<DescriptorChain<'a>, CracenError> {
+    if N != hash_out_len(alg) {
+        return Err(CracenError::InvalidInput);
+    }
+    let fragments = inputs.iter().filter(|input| !input.is_empty()).count();
+    if fragments > MAX_HASH_FRAGMENTS {
+        return Err(CracenError::InvalidInput);
+    }
 
-    info!("in_desc at {:02x}: {}", &in_desc as *const SxDesc, in_desc);
-    info!(
-        "mid_desc at {:02x}: {}",
-        &mid_desc as *const SxDesc, mid_desc
-    );
-    info!(
-        "out_desc at {:02x}: {}",
-        &out_desc as *const SxDesc, out_desc
-    );
-
-    // Enable cryptomaster
-    p.global_cracen_s.enable().write(|w| {
-        w.cryptomaster().set_bit();
-        w.rng().set_bit();
-        w.pkeikg().set_bit()
-    });
-
-    // Configure DMA source
-    dma.fetchaddrlsb()
-        .write(|w| unsafe { w.bits((&mut in_desc) as *mut _ as u32) });
-
-    // Configure DMA sink
-    dma.pushaddrlsb()
-        .write(|w| unsafe { w.bits((&mut out_desc) as *mut _ as u32) });
-
-    dma.config().write(|w| {
-        w.fetchctrlindirect().set_bit();
-        w.pushctrlindirect().set_bit();
-        w.fetchstop().clear_bit();
-        w.pushstop().clear_bit();
-        w.softrst().clear_bit()
-    });
-
-    // Start DMA
-    dma.start().write(|w| {
-        w.startfetch().set_bit();
-        w.startpush().set_bit()
-    });
-
-    // Wait
-    while dma.status().read().fetchbusy().bit_is_set() {}
-    while dma.status().read().pushbusy().bit_is_set() {}
-
-    Ok(())
+    let mut chain = DescriptorChain::new();
+    chain.push_fetch(header, TAG_HASH_HEADER);
+    if fragments == 0 {
+        // Empty message: the engine still expects a (fully ignored) data word
+        chain.push_fetch_padded(&[], TAG_HASH_DATA_LAST);
+    }
+    // One descriptor per input slice, the last one closes the message
+    let mut remaining = fragments;
+    for input in inputs.iter().filter(|input| !input.is_empty()) {
+        remaining -= 1;
+        let tag = if remaining == 0 {
+            TAG_HASH_DATA_LAST
+        } else {
+            TAG_HASH_DATA
+        };
+        chain.push_fetch_padded(input, tag);
+    }
+    chain.push_sink(out, TAG_SINK);
+    Ok(chain)
 }
 
-#[allow(
-    clippy::manual_dangling_ptr,
-    reason = "nRF54L15 uses 1 as last-descriptor sentinel"
-)]
-const LAST_DESC_PTR: *mut SxDesc = 1 as *mut SxDesc;
-
-#[derive(defmt::Format)]
+/// Incremental hash over any [`HashAlg`].
+///
+/// Whole blocks are pushed through the engine as soon as they are available
+/// and only the chaining value plus a partial block are kept in between
+/// calls. `finalize` pads the message in software, so the result matches a
+/// one-shot [`Cracen::hash`] over the concatenated input.
+#[derive(Clone, defmt::Format)]
 pub struct HashState {
     algorithm: HashAlg,
-    state: Option<[u8; 32]>,
-    block: [u8; 64],
+    state: Option<[u8; MAX_HASH_STATE_LEN]>,
+    block: [u8; MAX_HASH_BLOCK_LEN],
     digested: usize,
     block_bytes_left: usize,
 }
@@ -785,100 +954,194 @@ impl HashState {
         Self {
             algorithm,
             state: None,
-            block: [0; 64],
+            block: [0; MAX_HASH_BLOCK_LEN],
             digested: 0,
-            block_bytes_left: 64,
+            block_bytes_left: hash_block_len(algorithm),
         }
     }
 
-    pub fn update(&mut self, data: &[u8]) -> () {
-        let block_bytes_used = 64 - self.block_bytes_left;
-
-        // Case 1: data fits entirely inside the current block
-        if data.len() <= self.block_bytes_left as usize {
-            self.block[block_bytes_used..block_bytes_used + data.len()].copy_from_slice(data);
-            self.block_bytes_left -= data.len();
+    pub fn algorithm(&self) -> HashAlg {
+        self.algorithm
+    }
 
-            return;
+    /// Serializes the context so hashing can resume later, e.g. after a
+    /// reboot. See [`HashState::import`].
+    ///
+    /// Layout (multi-byte fields little endian):
+    /// version, algorithm, has-state flag, reserved, bytes digested (u64),
+    /// buffered byte count (u32), chaining value (64), buffered block (128),
+    /// CRC-32 of everything before it.
+    pub fn export(&self) -> [u8; HASH_STATE_EXPORT_LEN] {
+        let mut out = [0u8; HASH_STATE_EXPORT_LEN];
+        let block_bytes_used = hash_block_len(self.algorithm) - self.block_bytes_left;
+
+        out[0] = HASH_STATE_EXPORT_VERSION;
+        out[1] = self.algorithm as u8;
+        out[2] = self.state.is_some() as u8;
+        out[4..12].copy_from_slice(&(self.digested as u64).to_le_bytes());
+        out[12..16].copy_from_slice(&(block_bytes_used as u32).to_le_bytes());
+        if let Some(state) = &self.state {
+            out[16..80].copy_from_slice(state);
         }
+        out[80..208].copy_from_slice(&self.block);
 
-        // Case 2: data does NOT fit
-        let p = unsafe { nrf54l15_app_pac::Peripherals::steal() };
-        let total = block_bytes_used + data.len();
-        let largest_block_end = total & !63; // round down to nearest multiple of 64
-        let take_from_data = largest_block_end.saturating_sub(block_bytes_used);
+        let crc = crc32(&out[..208]);
+        out[208..].copy_from_slice(&crc.to_le_bytes());
+        out
+    }
 
-        let dma = p.global_cracencore_s.cryptmstrdma();
+    /// Restores a context produced by [`HashState::export`]. Rejects data
+    /// with a different version, a bad checksum or inconsistent fields.
+    pub fn import(data: &[u8]) -> Result<Self, CracenError> {
+        let data: &[u8; HASH_STATE_EXPORT_LEN] =
+            data.try_into().map_err(|_| CracenError::InvalidInput)?;
 
-        let mut new_state: [u8; 32] = [0x00; 32];
+        let crc = u32::from_le_bytes([data[208], data[209], data[210], data[211]]);
+        if data[0] != HASH_STATE_EXPORT_VERSION || crc != crc32(&data[..208]) {
+            return Err(CracenError::InvalidInput);
+        }
 
-        // 4-byte algorithm header
-        let header: [u8; 4] = [0x08, 0x00, 0x00, 0x00];
+        let algorithm = HashAlg::try_from(data[1])?;
+        let block_len = hash_block_len(algorithm);
 
-        let mut out_desc = SxDesc {
-            addr: new_state.as_mut_ptr(),
-            next: LAST_DESC_PTR,
-            sz: sz(32),
-            dmatag: 32,
-        };
+        let mut digested = [0u8; 8];
+        digested.copy_from_slice(&data[4..12]);
+        let digested =
+            usize::try_from(u64::from_le_bytes(digested)).map_err(|_| CracenError::InvalidInput)?;
+        let block_bytes_used =
+            u32::from_le_bytes([data[12], data[13], data[14], data[15]]) as usize;
 
-        let mut some_desc = SxDesc {
-            addr: data.as_ptr() as *mut u8,
-            next: LAST_DESC_PTR,
-            sz: 0x2000_0000 | take_from_data as u32, // 63
-            dmatag: 35,
+        let has_state = match data[2] {
+            0 => false,
+            1 => true,
+            _ => return Err(CracenError::InvalidInput),
         };
+        // A chaining value exists exactly when whole blocks were processed
+        if block_bytes_used >= block_len || digested % block_len != 0 || has_state != (digested > 0)
+        {
+            return Err(CracenError::InvalidInput);
+        }
+
+        let state = has_state.then(|| {
+            let mut state = [0u8; MAX_HASH_STATE_LEN];
+            state.copy_from_slice(&data[16..80]);
+            state
+        });
+        let mut block = [0u8; MAX_HASH_BLOCK_LEN];
+        block[..block_bytes_used].copy_from_slice(&data[80..80 + block_bytes_used]);
+
+        Ok(Self {
+            algorithm,
+            state,
+            block,
+            digested,
+            block_bytes_left: block_len - block_bytes_used,
+        })
+    }
 
-        let mut data_desc = SxDesc {
-            addr: self.block.as_ptr() as *mut u8,
-            next: &mut some_desc,
-            sz: block_bytes_used as u32,
-            dmatag: 3,
+    pub fn update(&mut self, cracen: &mut Cracen, data: &[u8]) -> Result<(), CracenError> {
+        let Some(take_from_data) = self.buffer(data) else {
+            return Ok(());
         };
 
-        let mut in_desc = SxDesc {
-            addr: header.as_ptr() as *mut u8,
-            next: &mut data_desc,
-            sz: sz(4),
-            dmatag: 19,
+        let header = self.update_header();
+        let mut new_state = [0u8; MAX_HASH_STATE_LEN];
+        self.update_chain(&header, &data[..take_from_data], &mut new_state)
+            .run(cracen)?;
+
+        self.absorbed(new_state, data, take_from_data);
+        Ok(())
+    }
+
+    /// Same as [`update`](Self::update), but sleeps until the CryptoMaster
+    /// interrupt signals completion. Needs [`cracen_enable_interrupt`].
+    pub async fn update_async(
+        &mut self,
+        cracen: &mut Cracen,
+        data: &[u8],
+    ) -> Result<(), CracenError> {
+        let Some(take_from_data) = self.buffer(data) else {
+            return Ok(());
         };
 
-        p.global_cracen_s.enable().write(|w| {
-            w.cryptomaster().set_bit();
-            w.rng().set_bit();
-            w.pkeikg().set_bit()
-        });
+        let header = self.update_header();
+        let mut new_state = [0u8; MAX_HASH_STATE_LEN];
+        self.update_chain(&header, &data[..take_from_data], &mut new_state)
+            .run_async(cracen)
+            .await?;
 
-        dma.fetchaddrlsb()
-            .write(|w| unsafe { w.bits((&mut in_desc) as *mut _ as u32) });
+        self.absorbed(new_state, data, take_from_data);
+        Ok(())
+    }
 
-        dma.pushaddrlsb()
-            .write(|w| unsafe { w.bits((&mut out_desc) as *mut _ as u32) });
+    // Case 1: data fits inside the current block without filling it. Any
+    // block that does fill up is hashed straight away, so between calls the
+    // buffer holds at most block_len - 1 bytes.
+    //
+    // Otherwise returns how many bytes of `data` complete whole blocks
+    // together with the buffered ones.
+    fn buffer(&mut self, data: &[u8]) -> Option<usize> {
+        let block_len = hash_block_len(self.algorithm);
+        let block_bytes_used = block_len - self.block_bytes_left;
+
+        if data.len() < self.block_bytes_left {
+            self.block[block_bytes_used..block_bytes_used + data.len()].copy_from_slice(data);
+            self.block_bytes_left -= data.len();
 
-        dma.config().write(|w| {
-            w.fetchctrlindirect().set_bit();
-            w.pushctrlindirect().set_bit();
-            w.fetchstop().clear_bit();
-            w.pushstop().clear_bit();
-            w.softrst().clear_bit()
-        });
+            return None;
+        }
 
-        dma.start().write(|w| {
-            w.startfetch().set_bit();
-            w.startpush().set_bit()
-        });
+        // Case 2: data does NOT fit
+        let total = block_bytes_used + data.len();
+        let largest_block_end = total - total % block_len; // round down to a block multiple
+        Some(largest_block_end - block_bytes_used)
+    }
 
-        while dma.status().read().fetchbusy().bit_is_set() {}
-        while dma.status().read().pushbusy().bit_is_set() {}
+    // 4-byte algorithm header: no padding, push the intermediate state
+    fn update_header(&self) -> [u8; 4] {
+        [self.algorithm as u8, 0x00, 0x00, 0x00]
+    }
 
-        info!("state: {:02x}", new_state);
+    fn update_chain<'a>(
+        &'a self,
+        header: &'a [u8; 4],
+        data: &'a [u8],
+        new_state: &'a mut [u8; MAX_HASH_STATE_LEN],
+    ) -> DescriptorChain<'a> {
+        let state_len = hash_state_len(self.algorithm);
+        let block_bytes_used = hash_block_len(self.algorithm) - self.block_bytes_left;
+
+        let mut chain = DescriptorChain::new();
+        chain.push_fetch(header, TAG_HASH_HEADER);
+        // Resume from the previous chaining value, if any
+        if let Some(state) = &self.state {
+            chain.push_fetch(&state[..state_len], TAG_HASH_STATE);
+        }
+        // Buffered bytes from a previous call go first
+        chain
+            .push_fetch(&self.block[..block_bytes_used], TAG_HASH_DATA)
+            .push_fetch(data, TAG_HASH_DATA_LAST)
+            .push_sink(&mut new_state[..state_len], TAG_SINK);
+        chain
+    }
+
+    // Bookkeeping once the first `take_from_data` bytes of `data` went
+    // through the engine together with the buffer
+    fn absorbed(
+        &mut self,
+        new_state: [u8; MAX_HASH_STATE_LEN],
+        data: &[u8],
+        take_from_data: usize,
+    ) {
+        let block_len = hash_block_len(self.algorithm);
+        let block_bytes_used = block_len - self.block_bytes_left;
 
         self.state = Some(new_state);
         self.digested += block_bytes_used + take_from_data;
 
         // reset buffer
-        self.block = [0u8; 64];
-        self.block_bytes_left = 64;
+        self.block = [0u8; MAX_HASH_BLOCK_LEN];
+        self.block_bytes_left = block_len;
 
         // copy leftover bytes into empty buffer
         let data_left = data.len() - take_from_data;
@@ -886,154 +1149,260 @@ impl HashState {
         self.block_bytes_left -= data_left;
     }
 
-    pub fn finalize(&mut self, p: &nrf54l15_app_pac::Peripherals) -> [u8; 32] {
-        let block_bytes_used = 64 - self.block_bytes_left;
-        let dma = p.global_cracencore_s.cryptmstrdma();
+    /// Writes the digest into `out`, which must be exactly
+    /// `hash_out_len(algorithm)` bytes long.
+    pub fn finalize<const N: usize>(
+        &mut self,
+        cracen: &mut Cracen,
+        out: &mut [u8; N],
+    ) -> Result<(), CracenError> {
+        self.finalize_slice(cracen, out)
+    }
 
-        let mut pad: [u8; 128] = [0x00; 128];
-        let padding_size = sha256_padding(self.digested + block_bytes_used, &mut pad);
+    /// Async version of [`finalize`](Self::finalize).
+    pub async fn finalize_async<const N: usize>(
+        &mut self,
+        cracen: &mut Cracen,
+        out: &mut [u8; N],
+    ) -> Result<(), CracenError> {
+        let header = self.finalize_header();
+        let mut pad = [0x00; 2 * MAX_HASH_BLOCK_LEN];
+        self.finalize_chain(&header, &mut pad, out)?
+            .run_async(cracen)
+            .await?;
+        Ok(())
+    }
 
-        let mut out: [u8; 32] = [0x00; 32];
+    fn finalize_slice(&mut self, cracen: &mut Cracen, out: &mut [u8]) -> Result<(), CracenError> {
+        let header = self.finalize_header();
+        let mut pad = [0x00; 2 * MAX_HASH_BLOCK_LEN];
+        self.finalize_chain(&header, &mut pad, out)?.run(cracen)?;
+        Ok(())
+    }
 
-        let mut out_desc = SxDesc {
-            addr: out.as_mut_ptr(),
-            next: LAST_DESC_PTR,
-            sz: sz(32),
-            dmatag: 32,
-        };
+    fn finalize_header(&self) -> [u8; 4] {
+        match self.state {
+            // Software padding, resume from the saved state
+            Some(_) => [self.algorithm as u8, 0x04, 0x00, 0x00],
+            // Nothing processed yet: let the engine pad the single block
+            None => [self.algorithm as u8, 0x06, 0x00, 0x00],
+        }
+    }
 
-        let header: [u8; 4] = match self.state {
-            Some(_) => [0x08, 0x04, 0x00, 0x00],
-            None => [0x08, 0x06, 0x00, 0x00],
-        };
+    fn finalize_chain<'a>(
+        &'a self,
+        header: &'a [u8; 4],
+        pad: &'a mut [u8; 2 * MAX_HASH_BLOCK_LEN],
+        out: &'a mut [u8],
+    ) -> Result<DescriptorChain<'a>, CracenError> {
+        if out.len() != hash_out_len(self.algorithm) {
+            return Err(CracenError::InvalidInput);
+        }
 
-        let mut some_desc = SxDesc {
-            addr: core::ptr::null_mut(),
-            next: core::ptr::null_mut(),
-            sz: 0,
-            dmatag: 0,
-        };
+        let block_len = hash_block_len(self.algorithm);
+        let state_len = hash_state_len(self.algorithm);
+        let block_bytes_used = block_len - self.block_bytes_left;
+
+        let padding_size = md_padding(
+            self.digested + block_bytes_used,
+            block_len,
+            hash_length_field_len(self.algorithm),
+            pad,
+        );
+        let pad: &'a [u8] = pad;
+
+        let mut chain = DescriptorChain::new();
+        chain.push_fetch(header, TAG_HASH_HEADER);
+        match &self.state {
+            None => {
+                chain.push_fetch_padded(&self.block[..block_bytes_used], TAG_HASH_DATA_LAST);
+            }
+            Some(state) => {
+                chain
+                    .push_fetch(&state[..state_len], TAG_HASH_STATE)
+                    .push_fetch(&self.block[..block_bytes_used], TAG_HASH_DATA)
+                    .push_fetch(&pad[..padding_size], TAG_HASH_DATA_LAST);
+            }
+        }
+        chain.push_sink(out, TAG_SINK);
+        Ok(chain)
+    }
+}
 
-        let mut data_desc = SxDesc {
-            addr: core::ptr::null_mut(),
-            next: core::ptr::null_mut(),
-            sz: 0,
-            dmatag: 0,
-        };
+// RustCrypto `digest` front-ends, so CRACEN hashing can be plugged into code
+// generic over the `digest` traits, like `SimpleHmac` or `SimpleHkdf`.
+//
+// They are `Default`, so they cannot carry a handle; every call borrows the
+// hash engine of the `SharedCracen` set with `use_for_digests`. The traits
+// cannot report errors, so the first one is latched: later updates are
+// skipped and the output comes out as zeros. Check `error()` or use
+// `try_finalize`.
+macro_rules! cracen_digest {
+    ($name:ident, $alg:expr, $out:ty, $block:ty) => {
+        #[doc = concat!("CRACEN-backed `digest` hasher for [`", stringify!($alg), "`].")]
+        ///
+        /// A `digest::Digest`, so it works with `SimpleHmac` and other
+        /// generic consumers. It hashes on the [`SharedCracen`] set with
+        /// [`SharedCracen::use_for_digests`], which must be called first.
+        ///
+        /// A hardware error during an update, or not getting the engine, is
+        /// latched: further updates do nothing, [`error`](Self::error)
+        /// returns it and the trait finalizers write zeros.
+        /// [`try_finalize`](Self::try_finalize) reports it instead. Only
+        /// [`Reset`](digest::Reset) clears it.
+        #[derive(Clone)]
+        pub struct $name {
+            state: HashState,
+            error: Option<CracenError>,
+        }
 
-        let mut state_desc = SxDesc {
-            addr: core::ptr::null_mut(),
-            next: core::ptr::null_mut(),
-            sz: 0,
-            dmatag: 0,
-        };
+        impl $name {
+            pub fn new() -> Self {
+                Self {
+                    state: HashState::init($alg),
+                    error: None,
+                }
+            }
 
-        let mut in_desc = SxDesc {
-            addr: core::ptr::null_mut(),
-            next: core::ptr::null_mut(),
-            sz: 0,
-            dmatag: 0,
-        };
+            /// The first hardware error since creation or the last reset.
+            pub fn error(&self) -> Option<CracenError> {
+                self.error
+            }
 
-        match self.state {
-            None => {
-                data_desc.addr = self.block.as_ptr() as *mut u8;
-                data_desc.next = LAST_DESC_PTR;
-                data_desc.sz = sz(2);
-                data_desc.dmatag = dmatag_for(2);
-
-                in_desc.addr = header.as_ptr() as *mut u8;
-                in_desc.next = &mut data_desc;
-                in_desc.sz = sz(4);
-                in_desc.dmatag = 19;
+            /// Like `finalize`, but returns a latched or final error instead
+            /// of a zeroed digest.
+            pub fn try_finalize(mut self) -> Result<digest::Output<Self>, CracenError> {
+                let mut out = digest::Output::<Self>::default();
+                self.finalize_inner(&mut out)?;
+                Ok(out)
             }
-            Some(state) => {
-                some_desc.addr = pad.as_ptr() as *mut u8;
-                some_desc.next = LAST_DESC_PTR;
-                some_desc.sz = 0x2000_0000 | padding_size as u32;
-                some_desc.dmatag = 35;
-
-                data_desc.addr = self.block.as_ptr() as *mut u8;
-                data_desc.next = &mut some_desc;
-                data_desc.sz = block_bytes_used as u32;
-                data_desc.dmatag = 3;
-
-                state_desc.addr = state.as_ptr() as *mut u8;
-                state_desc.next = &mut data_desc;
-                state_desc.sz = sz(32);
-                state_desc.dmatag = 99;
-
-                in_desc.addr = header.as_ptr() as *mut u8;
-                in_desc.next = &mut state_desc;
-                in_desc.sz = sz(4);
-                in_desc.dmatag = 19;
+
+            fn finalize_inner(&mut self, out: &mut [u8]) -> Result<(), CracenError> {
+                if let Some(e) = self.error {
+                    return Err(e);
+                }
+                // `OutputSize` always matches the algorithm, so only getting
+                // the engine or a DMA fault can fail
+                let state = &mut self.state;
+                let result = shared::with_digest_cracen(|cracen| state.finalize_slice(cracen, out));
+                self.state = HashState::init($alg);
+                if let Err(e) = result {
+                    self.error = Some(e);
+                }
+                result
+            }
+
+            fn finalize_or_zero(&mut self, out: &mut [u8]) {
+                if self.finalize_inner(out).is_err() {
+                    out.fill(0);
+                }
             }
         }
 
-        // Enable cryptomaster
-        p.global_cracen_s.enable().write(|w| {
-            w.cryptomaster().set_bit();
-            w.rng().set_bit();
-            w.pkeikg().set_bit()
-        });
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
 
-        // Configure DMA source
-        dma.fetchaddrlsb()
-            .write(|w| unsafe { w.bits((&mut in_desc) as *mut _ as u32) });
+        impl digest::HashMarker for $name {}
 
-        // Configure DMA sink
-        dma.pushaddrlsb()
-            .write(|w| unsafe { w.bits((&mut out_desc) as *mut _ as u32) });
+        impl digest::OutputSizeUser for $name {
+            type OutputSize = $out;
+        }
 
-        dma.config().write(|w| {
-            w.fetchctrlindirect().set_bit();
-            w.pushctrlindirect().set_bit();
-            w.fetchstop().clear_bit();
-            w.pushstop().clear_bit();
-            w.softrst().clear_bit()
-        });
+        impl digest::core_api::BlockSizeUser for $name {
+            type BlockSize = $block;
+        }
 
-        // Start DMA
-        dma.start().write(|w| {
-            w.startfetch().set_bit();
-            w.startpush().set_bit()
-        });
+        impl digest::Update for $name {
+            fn update(&mut self, data: &[u8]) {
+                if self.error.is_none() {
+                    let state = &mut self.state;
+                    if let Err(e) = shared::with_digest_cracen(|cracen| state.update(cracen, data))
+                    {
+                        self.error = Some(e);
+                    }
+                }
+            }
+        }
 
-        // Wait
-        while dma.status().read().fetchbusy().bit_is_set() {}
-        while dma.status().read().pushbusy().bit_is_set() {}
+        impl digest::FixedOutput for $name {
+            fn finalize_into(mut self, out: &mut digest::Output<Self>) {
+                self.finalize_or_zero(out);
+            }
+        }
 
-        info!("out: {:02x}", out);
-        out
-    }
+        impl digest::Reset for $name {
+            fn reset(&mut self) {
+                self.state = HashState::init($alg);
+                self.error = None;
+            }
+        }
+
+        impl digest::FixedOutputReset for $name {
+            // Restarts the hash but keeps a latched error, so the zeroed
+            // output can still be told apart from a real digest
+            fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+                self.finalize_or_zero(out);
+            }
+        }
+    };
 }
 
+cracen_digest!(CracenSha1, HashAlg::Sha1, U20, U64);
+cracen_digest!(CracenSha224, HashAlg::Sha2_224, U28, U64);
+cracen_digest!(CracenSha256, HashAlg::Sha2_256, U32, U64);
+cracen_digest!(CracenSha384, HashAlg::Sha2_384, U48, U128);
+cracen_digest!(CracenSha512, HashAlg::Sha2_512, U64, U128);
+cracen_digest!(CracenSm3, HashAlg::Sm3, U32, U64);
+
 pub fn sha256_padding(msg_len: usize, out: &mut [u8; 128]) -> usize {
+    md_padding(msg_len, 64, 8, out)
+}
+
+pub fn sha512_padding(msg_len: usize, out: &mut [u8; 256]) -> usize {
+    md_padding(msg_len, 128, 16, out)
+}
+
+// CRC-32 (IEEE 802.3, reflected), used to detect corrupted exported states
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+// Merkle–Damgård padding: 0x80, zeros, then the message length in bits as a
+// big-endian integer of `len_field` bytes, ending on a block boundary.
+fn md_padding(msg_len: usize, block_len: usize, len_field: usize, out: &mut [u8]) -> usize {
     out[0] = 0x80;
 
     // compute zero padding length
-    let mod_len = (msg_len + 1) % 64;
+    let mod_len = (msg_len + 1) % block_len;
+    let length_start = block_len - len_field;
 
-    let zero_pad_len = if mod_len <= 56 {
+    let zero_pad_len = if mod_len <= length_start {
         // fits in one block
-        56 - mod_len
+        length_start - mod_len
     } else {
         // needs two blocks
-        64 + 56 - mod_len
+        block_len + length_start - mod_len
     };
 
-    for i in 1..=zero_pad_len {
-        out[i] = 0;
-    }
+    out[1..=zero_pad_len].fill(0);
 
-    // append 64-bit length (big endian)
-    let bit_len = (msg_len as u64) * 8;
+    // append the bit length (big endian)
+    let bit_len = (msg_len as u128) * 8;
     let be = bit_len.to_be_bytes();
 
     let length_pos = 1 + zero_pad_len;
-    for i in 0..8 {
-        out[length_pos + i] = be[i];
-    }
+    out[length_pos..length_pos + len_field].copy_from_slice(&be[be.len() - len_field..]);
 
-    1 + zero_pad_len + 8
+    1 + zero_pad_len + len_field
 }
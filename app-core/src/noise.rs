@@ -0,0 +1,76 @@
+//! Raw noise export for SP 800-90B entropy assessment.
+
+use core::ops::ControlFlow;
+
+use crate::{Cracen, CracenError, Engine, restart_health_tests, wait_until};
+
+impl Cracen {
+    /// Streams raw samples from the TRNG noise source into `sink` until it
+    /// returns [`ControlFlow::Break`].
+    ///
+    /// The conditioning function is bypassed, so the FIFO words carry the
+    /// unprocessed ring oscillator bits, sampled every `clock_divider + 1`
+    /// cycles. `sink` gets whatever the FIFO holds at each poll, at least one
+    /// word. None of this goes through the health tests; it is meant for
+    /// offline analysis only, never as key material. The RNG control and
+    /// clock divider settings found on entry are restored before returning,
+    /// on errors too.
+    pub fn rng_raw_noise(
+        &mut self,
+        clock_divider: u32,
+        mut sink: impl FnMut(&[u32]) -> ControlFlow<()>,
+    ) -> Result<(), CracenError> {
+        let _power = self.power(Engine::Rng)?;
+        let rng = self.global_cracencore_s.rngcontrol();
+        let _restore = RestoreRng {
+            cracen: self,
+            control: rng.control().read().bits(),
+            clkdiv: rng.clkdiv().read().bits(),
+        };
+
+        // The divider only takes effect while the source is stopped
+        rng.control().write(|w| w.enable().clear_bit());
+        rng.clkdiv().write(|w| unsafe { w.bits(clock_divider) });
+        rng.control().write(|w| {
+            w.condbypass().set_bit();
+            w.enable().set_bit()
+        });
+
+        let mut words = [0u32; 32];
+        loop {
+            let mut level = 0;
+            wait_until(|| {
+                level = rng.fifolevel().read().bits() as usize;
+                level > 0
+            })?;
+
+            let level = level.min(words.len());
+            for (i, word) in words[..level].iter_mut().enumerate() {
+                *word = rng.fifo(i).read().bits();
+            }
+            if sink(&words[..level]).is_break() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+// Puts the RNG configuration found by `rng_raw_noise` back when dropped
+struct RestoreRng<'c> {
+    cracen: &'c Cracen,
+    control: u32,
+    clkdiv: u32,
+}
+
+impl Drop for RestoreRng<'_> {
+    fn drop(&mut self) {
+        let rng = self.cracen.global_cracencore_s.rngcontrol();
+        // Stopped first, as for the capture
+        rng.control().write(|w| w.enable().clear_bit());
+        rng.clkdiv().write(|w| unsafe { w.bits(self.clkdiv) });
+        rng.control().write(|w| unsafe { w.bits(self.control) });
+        // Make the next `rng` call run the startup test on the restarted
+        // source
+        restart_health_tests();
+    }
+}
@@ -0,0 +1,350 @@
+//! Command layer for the PK engine (BA414EP) microcode.
+//!
+//! Every PK operation follows the same sequence: select a microcode entry
+//! point and curve, copy the big-endian operands into the engine's data RAM,
+//! point it at them, start it and read the results back. [`PkJob`] does each
+//! step once, so an operation only has to say which [`PkCommand`] it runs and
+//! which [`Slot`]s hold what.
+
+use crate::{Cracen, CracenError, Engine, HashAlg, PowerGuard, wait_until};
+
+/// Start of the PK data RAM, [`SLOT_COUNT`] slots of [`SLOT_SIZE`] bytes.
+const DATA_RAM: u32 = 0x5180_8000;
+
+/// Size of one operand slot in the PK data RAM.
+pub const SLOT_SIZE: usize = 0x200;
+
+/// Number of operand slots; the microcode sits right after the last one.
+pub const SLOT_COUNT: u8 = 32;
+
+// ERRORFLAGS bits, i.e. STATUS[15:4] of the BA414EP
+const ERR_POINT_A_NOT_ON_CURVE: u32 = 1 << 0;
+const ERR_POINT_A_AT_INFINITY: u32 = 1 << 1;
+const ERR_POINT_B_NOT_ON_CURVE: u32 = 1 << 2;
+const ERR_POINT_B_AT_INFINITY: u32 = 1 << 3;
+const ERR_NOT_INVERTIBLE: u32 = 1 << 4;
+const ERR_SIGNATURE_INVALID: u32 = 1 << 6;
+
+/// Group order n of P-256, big-endian.
+const P256_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xbc, 0xe6, 0xfa, 0xad, 0xa7, 0x17, 0x9e, 0x84, 0xf3, 0xb9, 0xca, 0xc2, 0xfc, 0x63, 0x25, 0x51,
+];
+
+/// Group order n of P-384, big-endian.
+const P384_ORDER: [u8; 48] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xc7, 0x63, 0x4d, 0x81, 0xf4, 0x37, 0x2d, 0xdf,
+    0x58, 0x1a, 0x0d, 0xb2, 0x48, 0xb0, 0xa7, 0x7a, 0xec, 0xec, 0x19, 0x6a, 0xcc, 0xc5, 0x29, 0x73,
+];
+
+/// Group order n of P-521, big-endian.
+const P521_ORDER: [u8; 66] = [
+    0x01, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xfa, 0x51, 0x86, 0x87, 0x83, 0xbf, 0x2f, 0x96, 0x6b, 0x7f, 0xcc, 0x01, 0x48, 0xf7, 0x09,
+    0xa5, 0xd0, 0x3b, 0xb5, 0xc9, 0xb8, 0x89, 0x9c, 0x47, 0xae, 0xbb, 0x6f, 0xb7, 0x1e, 0x91, 0x38,
+    0x64, 0x09,
+];
+
+/// Group order L of Curve25519 and Ed25519, big-endian.
+const CURVE25519_ORDER: [u8; 32] = [
+    0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x14, 0xde, 0xf9, 0xde, 0xa2, 0xf7, 0x9c, 0xd6, 0x58, 0x12, 0x63, 0x1a, 0x5c, 0xf5, 0xd3, 0xed,
+];
+
+/// Microcode entry points, written to `COMMAND.OPEADDR`.
+///
+/// The operand layout each one expects is relative to the three slot
+/// pointers returned by [`pointers`](Self::pointers).
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub enum PkCommand {
+    /// A = point, result at C
+    EcPointDouble = 0x20,
+    /// A = first point, B = second point, result at C
+    EcPointAdd = 0x21,
+    /// A = point, B = scalar, result at C
+    EcPointMul = 0x22,
+    /// Fails unless the point at A lies on the curve
+    EcPointCheck = 0x26,
+    /// Edwards base point times a 64-byte scalar in A, A+1 (low half
+    /// first), reduced mod L; x, y at C
+    EddsaPointMul = 0x27,
+    /// Montgomery ladder on u-coordinates; A = u, B = scalar, result at C
+    MontgomeryMul = 0x28,
+    /// S = r + k * s mod L, with k at A, A+1, r at B, B+1 and the secret
+    /// scalar s at 12; S at C
+    EddsaSign = 0x2D,
+    /// Checks [S]B = R + [k]A, with k at A, A+1, then the encoded public key,
+    /// S and the encoded R at B, B+1 and B+2
+    EddsaVerify = 0x2E,
+    /// Private key at 6, nonce at 7, hash at 12; r, s at C
+    EcdsaSign = 0x30,
+    /// Public key at B, r, s at C, hash at 12
+    EcdsaVerify = 0x31,
+}
+
+impl PkCommand {
+    /// Slots for `POINTERS.OPPTRA`, `OPPTRB` and `OPPTRC`.
+    pub const fn pointers(self) -> (Slot, Slot, Slot) {
+        match self {
+            Self::EcPointDouble
+            | Self::EcPointAdd
+            | Self::EcPointMul
+            | Self::EcPointCheck
+            | Self::MontgomeryMul => (Slot::new(12), Slot::new(8), Slot::new(10)),
+            Self::EcdsaSign | Self::EcdsaVerify => (Slot::new(0), Slot::new(8), Slot::new(10)),
+            Self::EddsaPointMul | Self::EddsaVerify => (Slot::new(8), Slot::new(10), Slot::new(10)),
+            Self::EddsaSign => (Slot::new(8), Slot::new(10), Slot::new(13)),
+        }
+    }
+}
+
+/// Curves the microcode handles, written to `COMMAND.SELCURVE`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub enum PkCurve {
+    P256,
+    P384,
+    P521,
+    Curve25519,
+    Ed25519,
+}
+
+impl PkCurve {
+    /// Size in bytes of a field element or scalar, which is also the
+    /// operand size of every command on this curve.
+    pub const fn op_size(self) -> usize {
+        match self {
+            Self::P256 => 32,
+            Self::P384 => 48,
+            Self::P521 => 66,
+            Self::Curve25519 | Self::Ed25519 => 32,
+        }
+    }
+
+    /// Bit length of the group order.
+    pub const fn bits(self) -> usize {
+        match self {
+            Self::P256 => 256,
+            Self::P384 => 384,
+            Self::P521 => 521,
+            Self::Curve25519 | Self::Ed25519 => 253,
+        }
+    }
+
+    /// Group order, big-endian and [`op_size`](Self::op_size) bytes long.
+    pub const fn order(self) -> &'static [u8] {
+        match self {
+            Self::P256 => &P256_ORDER,
+            Self::P384 => &P384_ORDER,
+            Self::P521 => &P521_ORDER,
+            Self::Curve25519 | Self::Ed25519 => &CURVE25519_ORDER,
+        }
+    }
+
+    /// The hash signatures on this curve use: the ECDSA pairing for the
+    /// NIST curves, SHA-512 as in Ed25519 for the 25519 curves.
+    pub const fn hash_alg(self) -> HashAlg {
+        match self {
+            Self::P256 => HashAlg::Sha2_256,
+            Self::P384 => HashAlg::Sha2_384,
+            Self::P521 | Self::Curve25519 | Self::Ed25519 => HashAlg::Sha2_512,
+        }
+    }
+
+    /// The NIST curve whose operands are `op_size` bytes long.
+    pub const fn with_op_size(op_size: usize) -> Option<Self> {
+        match op_size {
+            32 => Some(Self::P256),
+            48 => Some(Self::P384),
+            66 => Some(Self::P521),
+            _ => None,
+        }
+    }
+
+    /// Clears the bits of a big-endian scalar above [`bits`](Self::bits);
+    /// among the NIST curves, only P-521 has any.
+    pub fn clamp_scalar(self, scalar: &mut [u8]) {
+        let excess = self.op_size() * 8 - self.bits();
+        if let Some(top) = scalar.first_mut() {
+            *top &= 0xFF >> excess;
+        }
+    }
+}
+
+/// An operand slot in the PK data RAM.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub struct Slot(u8);
+
+impl Slot {
+    pub const fn new(index: u8) -> Self {
+        assert!(index < SLOT_COUNT, "PK slot out of range");
+        Self(index)
+    }
+
+    pub const fn index(self) -> u8 {
+        self.0
+    }
+
+    /// The slot after this one, where a point keeps its y coordinate.
+    pub const fn next(self) -> Self {
+        Self::new(self.0 + 1)
+    }
+
+    // Operands are right-aligned: they end where the slot ends
+    fn addr(self, op_size: usize) -> u32 {
+        DATA_RAM + u32::from(self.0) * SLOT_SIZE as u32 + (SLOT_SIZE - op_size) as u32
+    }
+}
+
+/// A configured PK operation, holding the engine powered until dropped.
+///
+/// ```ignore
+/// let mut job = cracen.pk_job(PkCommand::EcPointMul, PkCurve::P256)?;
+/// job.write(Slot::new(8), k)?;
+/// job.write_point(Slot::new(12), px, py)?;
+/// job.run()?;
+/// job.read_point(Slot::new(10), out_x, out_y)?;
+/// ```
+///
+/// Operands are big-endian byte strings of exactly the curve's
+/// [`op_size`](PkCurve::op_size); the engine is set up to swap them itself.
+pub struct PkJob<'a> {
+    cracen: &'a mut Cracen,
+    command: PkCommand,
+    op_size: usize,
+    _power: PowerGuard,
+}
+
+impl Cracen {
+    /// Powers the PK engine and selects `command` on `curve`.
+    pub fn pk_job(&mut self, command: PkCommand, curve: PkCurve) -> Result<PkJob<'_>, CracenError> {
+        let power = self.power(Engine::Pke)?;
+        let core = &self.global_cracencore_s;
+        pk_wait(core)?;
+
+        let op_size = curve.op_size();
+        core.pk().command().write(|w| unsafe {
+            w.opeaddr().bits((command as u8).into());
+            w.opbytesm1().bits((op_size as u16 - 1).into());
+            match curve {
+                PkCurve::P256 => w.selcurve().p256(),
+                PkCurve::P384 => w.selcurve().p384(),
+                PkCurve::P521 => w.selcurve().p521(),
+                PkCurve::Curve25519 => w.selcurve().curve25519(),
+                PkCurve::Ed25519 => w.selcurve().ed25519(),
+            };
+            w.swapbytes().set_bit()
+        });
+        pk_wait(core)?;
+
+        Ok(PkJob {
+            cracen: self,
+            command,
+            op_size,
+            _power: power,
+        })
+    }
+}
+
+impl PkJob<'_> {
+    /// Copies the big-endian operand `value` into `slot`.
+    pub fn write(&mut self, slot: Slot, value: &[u8]) -> Result<(), CracenError> {
+        if value.len() != self.op_size {
+            return Err(CracenError::InvalidInput);
+        }
+        // Zero-pad the front up to a whole word so the copy stays aligned
+        let pad = (4 - self.op_size % 4) % 4;
+        let mut p = (slot.addr(self.op_size) - pad as u32) as *mut u32;
+        let mut word = [0u8; 4];
+        for (i, b) in core::iter::repeat_n(&0, pad).chain(value).enumerate() {
+            word[i % 4] = *b;
+            if i % 4 == 3 {
+                unsafe {
+                    core::ptr::write_volatile(p, u32::from_le_bytes(word));
+                    p = p.add(1);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a point as x in `slot` and y in the slot after it.
+    pub fn write_point(&mut self, slot: Slot, x: &[u8], y: &[u8]) -> Result<(), CracenError> {
+        self.write(slot, x)?;
+        self.write(slot.next(), y)
+    }
+
+    /// Starts the command on the operands written so far and waits for it.
+    ///
+    /// Error flags are decoded into [`CracenError::PointNotOnCurve`] and
+    /// friends where they have a meaning of their own, otherwise they come
+    /// back as [`CracenError::Pk`].
+    pub fn run(&mut self) -> Result<(), CracenError> {
+        let core = &self.cracen.global_cracencore_s;
+        let (a, b, c) = self.command.pointers();
+        core.pk().pointers().write(|w| unsafe {
+            w.opptra().bits(a.index().into());
+            w.opptrb().bits(b.index().into());
+            w.opptrc().bits(c.index().into())
+        });
+        core.pk().control().write(|w| {
+            w.start().set_bit();
+            w.clearirq().set_bit()
+        });
+        pk_wait(core)?;
+
+        let status = core.pk().status().read();
+        let error_flags = u32::from(status.errorflags().bits());
+        let fail_ptr = u32::from(status.failptr().bits());
+        match error_flags {
+            0 if fail_ptr == 0 => Ok(()),
+            f if f & (ERR_POINT_A_NOT_ON_CURVE | ERR_POINT_B_NOT_ON_CURVE) != 0 => {
+                Err(CracenError::PointNotOnCurve)
+            }
+            f if f & (ERR_POINT_A_AT_INFINITY | ERR_POINT_B_AT_INFINITY) != 0 => {
+                Err(CracenError::PointAtInfinity)
+            }
+            ERR_NOT_INVERTIBLE => Err(CracenError::NotInvertible),
+            ERR_SIGNATURE_INVALID => Err(CracenError::InvalidSignature),
+            _ => Err(CracenError::Pk {
+                error_flags,
+                fail_ptr,
+            }),
+        }
+    }
+
+    /// Copies the big-endian operand in `slot` into `out`.
+    pub fn read(&self, slot: Slot, out: &mut [u8]) -> Result<(), CracenError> {
+        if out.len() != self.op_size {
+            return Err(CracenError::InvalidInput);
+        }
+        let pad = (4 - self.op_size % 4) % 4;
+        let mut p = (slot.addr(self.op_size) - pad as u32) as *const u32;
+        let mut word = [0u8; 4];
+        for i in 0..pad + out.len() {
+            if i % 4 == 0 {
+                word = unsafe { core::ptr::read_volatile(p) }.to_le_bytes();
+                p = unsafe { p.add(1) };
+            }
+            if i >= pad {
+                out[i - pad] = word[i % 4];
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a point from `slot` and the slot after it.
+    pub fn read_point(&self, slot: Slot, x: &mut [u8], y: &mut [u8]) -> Result<(), CracenError> {
+        self.read(slot, x)?;
+        self.read(slot.next(), y)
+    }
+}
+
+// Waits for the PK engine, and the IKG DRBG it draws blinding values from,
+// to go idle
+pub(crate) fn pk_wait(core: &nrf54l15_app_pac::GlobalCracencoreS) -> Result<(), CracenError> {
+    wait_until(|| core.pk().status().read().pkbusy().bit_is_clear())?;
+    wait_until(|| core.ikg().status().read().ctrdrbgbusy().bit_is_clear())
+}
@@ -0,0 +1,236 @@
+//! ECDSA nonce generation: straight from the TRNG, deterministic per
+//! RFC 6979, or hedged (RFC 6979 with fresh entropy mixed in).
+
+use crate::keys::{is_zero, less_than};
+use crate::{Cracen, CracenError, HashAlg, Hmac, MAX_HASH_OUT_LEN, PkCurve, hash_out_len, wipe};
+
+/// TRNG bytes passed as the additional data k' of RFC 6979 3.6 for a
+/// [`NonceMode::Hedged`] nonce; 32 bytes cover the 256-bit security level
+/// of P-521, the strongest curve supported.
+pub const HEDGE_ENTROPY_LEN: usize = 32;
+
+/// How ECDSA signing picks its per-signature nonce k.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub enum NonceMode {
+    /// k straight from the TRNG. A biased or stuck TRNG leaks the key.
+    Random,
+    /// k from HMAC-DRBG over the key and the message hash (RFC 6979 3.2).
+    /// Reproducible, and independent of the TRNG.
+    Deterministic,
+    /// As `Deterministic`, with [`HEDGE_ENTROPY_LEN`] TRNG bytes as the
+    /// additional data of RFC 6979 3.6. Safe as long as either the key or
+    /// the TRNG is, and resists faults that replay a deterministic k.
+    Hedged,
+}
+
+impl Cracen {
+    // Draws k for signing `digest`, an `alg` hash, with `priv_key` on `curve`
+    pub(crate) fn ecdsa_nonce<const N: usize>(
+        &mut self,
+        mode: NonceMode,
+        curve: PkCurve,
+        alg: HashAlg,
+        priv_key: &[u8; N],
+        digest: &[u8],
+    ) -> Result<[u8; N], CracenError> {
+        match mode {
+            NonceMode::Random => {
+                // Redrawn until it lands in [1, n-1], like a private key
+                let mut k = [0u8; N];
+                loop {
+                    self.rng(&mut k)?;
+                    curve.clamp_scalar(&mut k);
+                    if is_valid_nonce(curve, &k) {
+                        return Ok(k);
+                    }
+                }
+            }
+            NonceMode::Deterministic => self.rfc6979_nonce(curve, alg, priv_key, digest, &[]),
+            NonceMode::Hedged => {
+                let mut extra = [0u8; HEDGE_ENTROPY_LEN];
+                self.rng(&mut extra)?;
+                let k = self.rfc6979_nonce(curve, alg, priv_key, digest, &extra);
+                wipe(&mut extra);
+                k
+            }
+        }
+    }
+
+    // RFC 6979 3.2, with HMAC over `alg`, the hash `digest` came from, and
+    // `extra` as the k' of 3.6
+    pub(crate) fn rfc6979_nonce<const N: usize>(
+        &mut self,
+        curve: PkCurve,
+        alg: HashAlg,
+        priv_key: &[u8; N],
+        digest: &[u8],
+        extra: &[u8],
+    ) -> Result<[u8; N], CracenError> {
+        let mut state = HmacDrbg {
+            alg,
+            curve,
+            k: [0; MAX_HASH_OUT_LEN],
+            v: [0; MAX_HASH_OUT_LEN],
+        };
+        let result = state.nonce(self, priv_key, digest, extra);
+        wipe(&mut state.k);
+        wipe(&mut state.v);
+        result
+    }
+}
+
+// The K and V of RFC 6979 3.2, as long as an `alg` digest
+struct HmacDrbg {
+    alg: HashAlg,
+    curve: PkCurve,
+    k: [u8; MAX_HASH_OUT_LEN],
+    v: [u8; MAX_HASH_OUT_LEN],
+}
+
+impl HmacDrbg {
+    fn nonce<const N: usize>(
+        &mut self,
+        cracen: &mut Cracen,
+        priv_key: &[u8; N],
+        digest: &[u8],
+        extra: &[u8],
+    ) -> Result<[u8; N], CracenError> {
+        let order = self.curve.order();
+        // bits2octets(h1): bits2int, then reduced mod q. h1 < 2^qlen < 2q,
+        // so one subtraction is enough.
+        let mut h = bits2int::<N>(self.curve, digest);
+        if !less_than(&h, order) {
+            sub_assign(&mut h, order);
+        }
+
+        // b, c: V = 0x01 0x01 ..., K = 0x00 0x00 ...
+        let len = hash_out_len(self.alg);
+        self.v[..len].fill(0x01);
+        self.k[..len].fill(0x00);
+
+        // d - g
+        for sep in [0x00, 0x01] {
+            self.update_k(cracen, &[&[sep], priv_key, &h, extra])?;
+            self.update_v(cracen)?;
+        }
+
+        // h
+        loop {
+            let mut t = [0u8; N];
+            for chunk in t.chunks_mut(len) {
+                self.update_v(cracen)?;
+                chunk.copy_from_slice(&self.v[..chunk.len()]);
+            }
+            let k = bits2int::<N>(self.curve, &t);
+            wipe(&mut t);
+            if is_valid_nonce(self.curve, &k) {
+                return Ok(k);
+            }
+            self.update_k(cracen, &[&[0x00]])?;
+            self.update_v(cracen)?;
+        }
+    }
+
+    // K = HMAC_K(V || parts...)
+    fn update_k(&mut self, cracen: &mut Cracen, parts: &[&[u8]]) -> Result<(), CracenError> {
+        let len = hash_out_len(self.alg);
+        let mut mac = Hmac::new(cracen, self.alg, &self.k[..len])?;
+        mac.update(cracen, &self.v[..len])?;
+        for part in parts {
+            mac.update(cracen, part)?;
+        }
+        mac.finalize_slice(cracen, &mut self.k[..len])
+    }
+
+    // V = HMAC_K(V)
+    fn update_v(&mut self, cracen: &mut Cracen) -> Result<(), CracenError> {
+        let len = hash_out_len(self.alg);
+        let mut mac = Hmac::new(cracen, self.alg, &self.k[..len])?;
+        mac.update(cracen, &self.v[..len])?;
+        mac.finalize_slice(cracen, &mut self.v[..len])
+    }
+}
+
+// 1 <= k < n. Masking k to the bit length of n first keeps the chance of a
+// redraw below 1/2.
+pub(crate) fn is_valid_nonce(curve: PkCurve, k: &[u8]) -> bool {
+    !is_zero(k) & less_than(k, curve.order())
+}
+
+/// RFC 6979 bits2int: the leftmost qlen bits of `bytes` as an integer,
+/// big-endian in the curve's operand size. Also the FIPS 186-5 rule for
+/// turning a hash longer than the order into the ECDSA input.
+pub(crate) fn bits2int<const N: usize>(curve: PkCurve, bytes: &[u8]) -> [u8; N] {
+    let mut out = [0u8; N];
+    if bytes.len() * 8 <= curve.bits() {
+        out[N - bytes.len()..].copy_from_slice(bytes);
+        return out;
+    }
+
+    // Keep the leftmost N bytes, then drop the bits past qlen
+    out.copy_from_slice(&bytes[..N]);
+    let shift = N * 8 - curve.bits();
+    if shift > 0 {
+        for i in (0..N).rev() {
+            let high = if i > 0 { out[i - 1] << (8 - shift) } else { 0 };
+            out[i] = (out[i] >> shift) | high;
+        }
+    }
+    out
+}
+
+// a -= b for big-endian values of the same length
+fn sub_assign(a: &mut [u8], b: &[u8]) {
+    let mut borrow = 0u16;
+    for (x, y) in a.iter_mut().zip(b).rev() {
+        let diff = u16::from(*x)
+            .wrapping_sub(u16::from(*y))
+            .wrapping_sub(borrow);
+        *x = diff as u8;
+        borrow = (diff >> 8) & 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // n - 1 and n, big-endian in the operand size
+    fn order_minus_one<const N: usize>(curve: PkCurve) -> [u8; N] {
+        let mut k: [u8; N] = curve.order().try_into().unwrap();
+        sub_assign(&mut k, &bits2int::<N>(curve, &[1]));
+        k
+    }
+
+    #[test]
+    fn nonce_range() {
+        let n: [u8; 32] = PkCurve::P256.order().try_into().unwrap();
+        assert!(!is_valid_nonce(PkCurve::P256, &[0; 32]));
+        assert!(is_valid_nonce(
+            PkCurve::P256,
+            &bits2int::<32>(PkCurve::P256, &[1])
+        ));
+        assert!(is_valid_nonce(
+            PkCurve::P256,
+            &order_minus_one::<32>(PkCurve::P256)
+        ));
+        assert!(!is_valid_nonce(PkCurve::P256, &n));
+        assert!(!is_valid_nonce(PkCurve::P256, &[0xff; 32]));
+
+        let n: [u8; 66] = PkCurve::P521.order().try_into().unwrap();
+        assert!(is_valid_nonce(
+            PkCurve::P521,
+            &order_minus_one::<66>(PkCurve::P521)
+        ));
+        assert!(!is_valid_nonce(PkCurve::P521, &n));
+    }
+
+    #[test]
+    fn masked_draw_of_all_ones_is_rejected() {
+        // 2^521 - 1 after masking, above the P-521 order
+        let mut k = [0xff; 66];
+        PkCurve::P521.clamp_scalar(&mut k);
+        assert_eq!(k[0], 0x01);
+        assert!(!is_valid_nonce(PkCurve::P521, &k));
+    }
+}
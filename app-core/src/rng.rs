@@ -0,0 +1,96 @@
+//! `rand_core` front-end for the CRACEN TRNG.
+
+use core::num::NonZeroU32;
+
+use crate::{Cracen, CracenError, PowerGuard};
+
+/// The CRACEN TRNG as a `rand_core` RNG, for RustCrypto APIs that take one
+/// (`p256::SecretKey::random`, randomized ECDSA signing, ...).
+///
+/// Implements `RngCore` + `CryptoRng` from rand_core 0.6, which the p256 0.13
+/// stack uses, and `TryRngCore` + `TryCryptoRng` from rand_core 0.9. The
+/// fallible methods report a stalled FIFO as [`CracenError::Timeout`] and a
+/// failed entropy health test as [`CracenError::HealthTest`].
+///
+/// # Panics
+///
+/// The rand_core 0.6 `next_u32`, `next_u64` and `fill_bytes` cannot return
+/// an error, so they **panic** on any TRNG failure rather than hand out
+/// bytes that may not be random. Code that must survive a health test
+/// failure should call `try_fill_bytes` (either version) instead.
+///
+/// The RNG stays powered for as long as the `CracenRng` exists, so a failed
+/// health test sticks until it is dropped.
+pub struct CracenRng<'a> {
+    cracen: &'a mut Cracen,
+    _power: PowerGuard,
+}
+
+impl<'a> CracenRng<'a> {
+    pub fn new(cracen: &'a mut Cracen) -> Self {
+        let _power = cracen.power_rng();
+        Self { cracen, _power }
+    }
+}
+
+impl rand_core::RngCore for CracenRng<'_> {
+    fn next_u32(&mut self) -> u32 {
+        let mut buf = [0u8; 4];
+        self.fill_bytes(&mut buf);
+        u32::from_le_bytes(buf)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0u8; 8];
+        self.fill_bytes(&mut buf);
+        u64::from_le_bytes(buf)
+    }
+
+    /// # Panics
+    ///
+    /// On any TRNG failure, see [`CracenRng`].
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(e) = self.try_fill_bytes(dest) {
+            panic!("CRACEN TRNG failed: {}", e);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.cracen.rng(dest).map_err(rand_core_error)
+    }
+}
+
+impl rand_core::CryptoRng for CracenRng<'_> {}
+
+impl rand_core_09::TryRngCore for CracenRng<'_> {
+    type Error = CracenError;
+
+    fn try_next_u32(&mut self) -> Result<u32, CracenError> {
+        let mut buf = [0u8; 4];
+        self.cracen.rng(&mut buf)?;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn try_next_u64(&mut self) -> Result<u64, CracenError> {
+        let mut buf = [0u8; 8];
+        self.cracen.rng(&mut buf)?;
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    fn try_fill_bytes(&mut self, dst: &mut [u8]) -> Result<(), CracenError> {
+        self.cracen.rng(dst)
+    }
+}
+
+impl rand_core_09::TryCryptoRng for CracenRng<'_> {}
+
+// rand_core 0.6 errors are bare codes in the range reserved for custom RNGs
+fn rand_core_error(err: CracenError) -> rand_core::Error {
+    let code = match err {
+        CracenError::Timeout => 1,
+        CracenError::HealthTest(_) => 2,
+        _ => 3,
+    };
+    let code = NonZeroU32::new(rand_core::Error::CUSTOM_START + code).unwrap();
+    rand_core::Error::from(code)
+}
@@ -0,0 +1,300 @@
+//! SHA-3 / SHAKE on the CRACEN Keccak engine (BA418).
+//!
+//! The engine is only used to absorb whole rate-sized blocks into the
+//! 1600-bit state, which it pushes back out after every call. Padding is
+//! done in software, which keeps one code path for one-shot hashing,
+//! streaming and XOF squeezing: squeezing another block is just absorbing
+//! a block of zeros into the saved state.
+
+use crate::{
+    Cracen, CracenError, DescriptorChain, TAG_CONFIG, TAG_ENGINE_BA418, TAG_LAST, TAG_SINK,
+    TAG_STATE,
+};
+
+const TAG_SHA3_HEADER: u32 = TAG_CONFIG | TAG_ENGINE_BA418;
+const TAG_SHA3_DATA: u32 = TAG_ENGINE_BA418;
+const TAG_SHA3_DATA_LAST: u32 = TAG_LAST | TAG_ENGINE_BA418;
+const TAG_SHA3_STATE: u32 = TAG_STATE | TAG_LAST | TAG_ENGINE_BA418;
+
+const KECCAK_STATE_LEN: usize = 200;
+const MAX_SHA3_RATE: usize = 168;
+
+#[derive(Copy, Clone, Debug, defmt::Format)]
+pub enum Sha3Alg {
+    Sha3_224,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    Shake128,
+    Shake256,
+    /// Original Keccak padding, as used by Ethereum
+    Keccak256,
+}
+
+// Capacity select written to the BA418 header
+const fn sha3_mode(alg: Sha3Alg) -> u8 {
+    match alg {
+        Sha3Alg::Sha3_224 => 0x01,
+        Sha3Alg::Sha3_256 | Sha3Alg::Shake256 | Sha3Alg::Keccak256 => 0x02,
+        Sha3Alg::Sha3_384 => 0x04,
+        Sha3Alg::Sha3_512 => 0x08,
+        Sha3Alg::Shake128 => 0x10,
+    }
+}
+
+// Bytes absorbed per permutation: 200 - 2 * security level
+const fn sha3_rate(alg: Sha3Alg) -> usize {
+    match alg {
+        Sha3Alg::Sha3_224 => 144,
+        Sha3Alg::Sha3_256 | Sha3Alg::Shake256 | Sha3Alg::Keccak256 => 136,
+        Sha3Alg::Sha3_384 => 104,
+        Sha3Alg::Sha3_512 => 72,
+        Sha3Alg::Shake128 => 168,
+    }
+}
+
+// Domain separation bits plus the first bit of pad10*1
+const fn sha3_domain(alg: Sha3Alg) -> u8 {
+    match alg {
+        Sha3Alg::Sha3_224 | Sha3Alg::Sha3_256 | Sha3Alg::Sha3_384 | Sha3Alg::Sha3_512 => 0x06,
+        Sha3Alg::Shake128 | Sha3Alg::Shake256 => 0x1f,
+        Sha3Alg::Keccak256 => 0x01,
+    }
+}
+
+/// Digest length of the fixed-output variants, `None` for SHAKE.
+pub const fn sha3_out_len(alg: Sha3Alg) -> Option<usize> {
+    match alg {
+        Sha3Alg::Sha3_224 => Some(28),
+        Sha3Alg::Sha3_256 | Sha3Alg::Keccak256 => Some(32),
+        Sha3Alg::Sha3_384 => Some(48),
+        Sha3Alg::Sha3_512 => Some(64),
+        Sha3Alg::Shake128 | Sha3Alg::Shake256 => None,
+    }
+}
+
+impl Cracen {
+    pub fn sha3_224(&mut self, input: &[u8], out: &mut [u8; 28]) -> Result<(), CracenError> {
+        self.sha3(input, out, Sha3Alg::Sha3_224)
+    }
+
+    pub fn sha3_256(&mut self, input: &[u8], out: &mut [u8; 32]) -> Result<(), CracenError> {
+        self.sha3(input, out, Sha3Alg::Sha3_256)
+    }
+
+    pub fn sha3_384(&mut self, input: &[u8], out: &mut [u8; 48]) -> Result<(), CracenError> {
+        self.sha3(input, out, Sha3Alg::Sha3_384)
+    }
+
+    pub fn sha3_512(&mut self, input: &[u8], out: &mut [u8; 64]) -> Result<(), CracenError> {
+        self.sha3(input, out, Sha3Alg::Sha3_512)
+    }
+
+    pub fn keccak256(&mut self, input: &[u8], out: &mut [u8; 32]) -> Result<(), CracenError> {
+        self.sha3(input, out, Sha3Alg::Keccak256)
+    }
+
+    /// SHAKE128 with any output length.
+    pub fn shake128(&mut self, input: &[u8], out: &mut [u8]) -> Result<(), CracenError> {
+        self.shake(input, out, Sha3Alg::Shake128)
+    }
+
+    /// SHAKE256 with any output length.
+    pub fn shake256(&mut self, input: &[u8], out: &mut [u8]) -> Result<(), CracenError> {
+        self.shake(input, out, Sha3Alg::Shake256)
+    }
+
+    pub fn sha3<const N: usize>(
+        &mut self,
+        input: &[u8],
+        out: &mut [u8; N],
+        alg: Sha3Alg,
+    ) -> Result<(), CracenError> {
+        let mut state = Sha3State::init(alg);
+        state.update(self, input)?;
+        state.finalize(self, out)
+    }
+
+    pub fn shake(&mut self, input: &[u8], out: &mut [u8], alg: Sha3Alg) -> Result<(), CracenError> {
+        let mut state = Sha3State::init(alg);
+        state.update(self, input)?;
+        state.finalize_xof(self)?.read(self, out)
+    }
+}
+
+/// Incremental SHA-3 / SHAKE, following the [`HashState`](crate::HashState)
+/// pattern: whole rate blocks go to the engine, the rest is buffered.
+#[derive(Clone, defmt::Format)]
+pub struct Sha3State {
+    algorithm: Sha3Alg,
+    state: Option<[u8; KECCAK_STATE_LEN]>,
+    block: [u8; MAX_SHA3_RATE],
+    block_bytes_left: usize,
+}
+
+impl Sha3State {
+    pub fn init(algorithm: Sha3Alg) -> Self {
+        Self {
+            algorithm,
+            state: None,
+            block: [0; MAX_SHA3_RATE],
+            block_bytes_left: sha3_rate(algorithm),
+        }
+    }
+
+    pub fn algorithm(&self) -> Sha3Alg {
+        self.algorithm
+    }
+
+    pub fn update(&mut self, cracen: &mut Cracen, data: &[u8]) -> Result<(), CracenError> {
+        let rate = sha3_rate(self.algorithm);
+        let block_bytes_used = rate - self.block_bytes_left;
+
+        // Case 1: data fits inside the current block without filling it
+        if data.len() < self.block_bytes_left {
+            self.block[block_bytes_used..block_bytes_used + data.len()].copy_from_slice(data);
+            self.block_bytes_left -= data.len();
+
+            return Ok(());
+        }
+
+        // Case 2: absorb every whole block, keep the tail
+        let total = block_bytes_used + data.len();
+        let take_from_data = total - total % rate - block_bytes_used;
+
+        absorb(
+            cracen,
+            self.algorithm,
+            &mut self.state,
+            &self.block[..block_bytes_used],
+            &data[..take_from_data],
+        )?;
+
+        // copy leftover bytes into empty buffer
+        let data_left = data.len() - take_from_data;
+        self.block = [0u8; MAX_SHA3_RATE];
+        self.block[..data_left].copy_from_slice(&data[take_from_data..]);
+        self.block_bytes_left = rate - data_left;
+        Ok(())
+    }
+
+    /// Writes the digest of a fixed-output variant into `out`, which must
+    /// be exactly [`sha3_out_len`] bytes long. Consumes the state, as the
+    /// padding can only be absorbed once.
+    pub fn finalize<const N: usize>(
+        mut self,
+        cracen: &mut Cracen,
+        out: &mut [u8; N],
+    ) -> Result<(), CracenError> {
+        if sha3_out_len(self.algorithm) != Some(N) {
+            return Err(CracenError::InvalidInput);
+        }
+
+        let state = self.pad_and_absorb(cracen)?;
+        out.copy_from_slice(&state[..N]);
+        Ok(())
+    }
+
+    /// Pads the message and returns a reader producing SHAKE output of any
+    /// length.
+    pub fn finalize_xof(mut self, cracen: &mut Cracen) -> Result<Sha3XofReader, CracenError> {
+        if sha3_out_len(self.algorithm).is_some() {
+            return Err(CracenError::InvalidInput);
+        }
+
+        let state = self.pad_and_absorb(cracen)?;
+
+        Ok(Sha3XofReader {
+            algorithm: self.algorithm,
+            state,
+            pos: 0,
+        })
+    }
+
+    fn pad_and_absorb(
+        &mut self,
+        cracen: &mut Cracen,
+    ) -> Result<[u8; KECCAK_STATE_LEN], CracenError> {
+        let rate = sha3_rate(self.algorithm);
+        let block_bytes_used = rate - self.block_bytes_left;
+
+        // pad10*1; both ends share a byte when only one byte is free
+        let mut block = self.block;
+        block[block_bytes_used..rate].fill(0);
+        block[block_bytes_used] = sha3_domain(self.algorithm);
+        block[rate - 1] |= 0x80;
+
+        absorb(cracen, self.algorithm, &mut self.state, &block[..rate], &[])?;
+
+        Ok(self.state.unwrap_or([0; KECCAK_STATE_LEN]))
+    }
+}
+
+/// Squeezes SHAKE output from a padded [`Sha3State`].
+#[derive(Clone, defmt::Format)]
+pub struct Sha3XofReader {
+    algorithm: Sha3Alg,
+    state: [u8; KECCAK_STATE_LEN],
+    pos: usize,
+}
+
+impl Sha3XofReader {
+    /// Fills `out` with the next output bytes. Can be called repeatedly to
+    /// continue the stream.
+    pub fn read(&mut self, cracen: &mut Cracen, out: &mut [u8]) -> Result<(), CracenError> {
+        let rate = sha3_rate(self.algorithm);
+        let zeros = [0u8; MAX_SHA3_RATE];
+        let mut idx = 0;
+
+        while idx < out.len() {
+            if self.pos == rate {
+                // Absorbing a zero block without padding is a bare permutation
+                let mut state = Some(self.state);
+                absorb(cracen, self.algorithm, &mut state, &zeros[..rate], &[])?;
+                self.state = state.unwrap_or(self.state);
+                self.pos = 0;
+            }
+
+            let take = (out.len() - idx).min(rate - self.pos);
+            out[idx..idx + take].copy_from_slice(&self.state[self.pos..self.pos + take]);
+            self.pos += take;
+            idx += take;
+        }
+
+        Ok(())
+    }
+}
+
+// Absorbs `head || tail` (a whole number of rate blocks) into `state`,
+// starting from the all-zero state when there is none yet.
+fn absorb(
+    cracen: &mut Cracen,
+    alg: Sha3Alg,
+    state: &mut Option<[u8; KECCAK_STATE_LEN]>,
+    head: &[u8],
+    tail: &[u8],
+) -> Result<(), CracenError> {
+    let mut new_state = [0u8; KECCAK_STATE_LEN];
+
+    // 4-byte header: capacity select, no hardware padding, push the state
+    let header: [u8; 4] = [sha3_mode(alg), 0x00, 0x00, 0x00];
+
+    let mut chain = DescriptorChain::new();
+    chain.push_fetch(&header, TAG_SHA3_HEADER);
+    if let Some(s) = state {
+        chain.push_fetch(s, TAG_SHA3_STATE);
+    }
+    // Skip empty pieces; whichever piece comes last carries the last tag
+    if tail.is_empty() {
+        chain.push_fetch(head, TAG_SHA3_DATA_LAST);
+    } else {
+        chain
+            .push_fetch(head, TAG_SHA3_DATA)
+            .push_fetch(tail, TAG_SHA3_DATA_LAST);
+    }
+    chain.push_sink(&mut new_state, TAG_SINK);
+    chain.run(cracen)?;
+
+    *state = Some(new_state);
+    Ok(())
+}
@@ -0,0 +1,347 @@
+//! CRACEN access shared between embassy tasks.
+
+use core::cell::Cell;
+
+use embassy_sync::blocking_mutex::raw::{CriticalSectionRawMutex, RawMutex};
+use embassy_sync::mutex::Mutex;
+
+use crate::{
+    Cracen, CracenError, HEDGE_ENTROPY_LEN, HashAlg, HashState, NonceMode, PkCurve, PowerGuard,
+    PrivateScalar, SharedSecret, hash_out_len, is_valid_nonce, prehash_alg, wipe,
+};
+
+/// [`Cracen`] behind one async lock per engine, for firmware where several
+/// tasks use it.
+///
+/// The CryptoMaster (hashing), the RNG and the PK engine each have their own
+/// mutex, so a signature can run while another task hashes, but two users of
+/// the same engine wait for each other. Operations that need more than one
+/// engine, like [`ecdsa_sign`](Self::ecdsa_sign), take the locks one after
+/// the other and never hold two at once. The one exception is
+/// [`rng`](Self::rng), which holds the PK lock as well, always taken after
+/// the RNG lock.
+///
+/// To share it between `'static` tasks, put it in a static, e.g. with
+/// `cortex_m::singleton!(: SharedCracen = SharedCracen::new(cracen))`.
+/// Hashing goes through the interrupt-driven DMA path, so
+/// [`cracen_enable_interrupt`](crate::cracen_enable_interrupt) must have been
+/// called.
+///
+/// The RNG stays powered for as long as the `SharedCracen` exists, like with
+/// a [`CracenRng`](crate::CracenRng).
+pub struct SharedCracen<M: RawMutex = CriticalSectionRawMutex> {
+    // One part of the split handle each, see `Cracen::split`
+    hash: Mutex<M, Cracen>,
+    rng: Mutex<M, Cracen>,
+    pke: Mutex<M, Cracen>,
+    rng_power: PowerGuard,
+}
+
+// The instance the `digest` front-ends hash on, see `use_for_digests`
+static DIGEST_CRACEN: critical_section::Mutex<Cell<Option<&'static SharedCracen>>> =
+    critical_section::Mutex::new(Cell::new(None));
+
+impl SharedCracen {
+    /// Makes this instance the one the RustCrypto `digest` front-ends
+    /// ([`CracenSha256`](crate::CracenSha256) and friends) hash on. Until
+    /// this is called they fail with [`CracenError::InvalidInput`].
+    ///
+    /// They take the hash lock without waiting, as the `digest` traits are
+    /// blocking, and fail with [`CracenError::Busy`] if another task holds
+    /// it.
+    pub fn use_for_digests(&'static self) {
+        critical_section::with(|cs| DIGEST_CRACEN.borrow(cs).set(Some(self)));
+    }
+}
+
+// Runs `f` on the hash engine of the instance set with `use_for_digests`
+pub(crate) fn with_digest_cracen<R>(
+    f: impl FnOnce(&mut Cracen) -> Result<R, CracenError>,
+) -> Result<R, CracenError> {
+    let shared = critical_section::with(|cs| DIGEST_CRACEN.borrow(cs).get())
+        .ok_or(CracenError::InvalidInput)?;
+    let mut cracen = shared.hash.try_lock().map_err(|_| CracenError::Busy)?;
+    f(&mut cracen)
+}
+
+impl<M: RawMutex> SharedCracen<M> {
+    pub fn new(cracen: Cracen) -> Self {
+        let [hash, rng, pke] = cracen.split();
+        Self {
+            hash: Mutex::new(hash),
+            rng_power: rng.power_rng(),
+            rng: Mutex::new(rng),
+            pke: Mutex::new(pke),
+        }
+    }
+
+    /// Gives the exclusive handle back. The RNG is powered down again.
+    pub fn into_inner(self) -> Cracen {
+        let Self {
+            hash,
+            rng,
+            pke,
+            rng_power,
+        } = self;
+        drop(rng_power);
+        Cracen::join([hash.into_inner(), rng.into_inner(), pke.into_inner()])
+    }
+
+    pub async fn hash<const N: usize>(
+        &self,
+        input: &[u8],
+        out: &mut [u8; N],
+        alg: HashAlg,
+    ) -> Result<(), CracenError> {
+        self.hash.lock().await.hash_async(input, out, alg).await
+    }
+
+    pub async fn hash_vectored<const N: usize>(
+        &self,
+        alg: HashAlg,
+        inputs: &[&[u8]],
+        out: &mut [u8; N],
+    ) -> Result<(), CracenError> {
+        self.hash
+            .lock()
+            .await
+            .hash_vectored_async(alg, inputs, out)
+            .await
+    }
+
+    /// Feeds `data` into an incremental hash. The lock is only held for this
+    /// call, so other tasks can hash in between.
+    pub async fn update(&self, state: &mut HashState, data: &[u8]) -> Result<(), CracenError> {
+        state.update_async(&mut *self.hash.lock().await, data).await
+    }
+
+    pub async fn finalize<const N: usize>(
+        &self,
+        state: &mut HashState,
+        out: &mut [u8; N],
+    ) -> Result<(), CracenError> {
+        state
+            .finalize_async(&mut *self.hash.lock().await, out)
+            .await
+    }
+
+    /// Same as [`Cracen::rng`]. The PK engine is locked for the duration
+    /// too, as its key generation draws from the RNG, which hands out raw
+    /// noise while `rng` runs.
+    pub async fn rng(&self, buf: &mut [u8]) -> Result<(), CracenError> {
+        let mut rng = self.rng.lock().await;
+        let _pke = self.pke.lock().await;
+        rng.rng(buf)
+    }
+
+    /// Same as [`Cracen::ecdsa_sign`]. The PK engine is only locked once the
+    /// message is hashed and the nonce drawn.
+    pub async fn ecdsa_sign<const N: usize>(
+        &self,
+        msg: &[u8],
+        priv_key: &impl PrivateScalar<N>,
+    ) -> Result<([u8; N], [u8; N]), CracenError> {
+        self.ecdsa_sign_with_nonce(msg, priv_key, NonceMode::Random)
+            .await
+    }
+
+    /// Same as [`Cracen::ecdsa_sign_with_nonce`].
+    pub async fn ecdsa_sign_with_nonce<const N: usize>(
+        &self,
+        msg: &[u8],
+        priv_key: &impl PrivateScalar<N>,
+        nonce: NonceMode,
+    ) -> Result<([u8; N], [u8; N]), CracenError> {
+        let curve = PkCurve::with_op_size(N).ok_or(CracenError::InvalidInput)?;
+        self.ecdsa_sign_with(curve.hash_alg(), msg, priv_key, nonce)
+            .await
+    }
+
+    /// Same as [`Cracen::ecdsa_sign_with`].
+    pub async fn ecdsa_sign_with<const N: usize>(
+        &self,
+        alg: HashAlg,
+        msg: &[u8],
+        priv_key: &impl PrivateScalar<N>,
+        nonce: NonceMode,
+    ) -> Result<([u8; N], [u8; N]), CracenError> {
+        let curve = PkCurve::with_op_size(N).ok_or(CracenError::InvalidInput)?;
+        let mut digest = [0u8; 64];
+        let digest = self.ecdsa_hash(alg, msg, &mut digest).await?;
+        self.ecdsa_sign_hashed(curve, alg, digest, priv_key.scalar(), nonce)
+            .await
+    }
+
+    /// Same as [`Cracen::ecdsa_sign_prehash`].
+    pub async fn ecdsa_sign_prehash<const N: usize>(
+        &self,
+        digest: &[u8],
+        priv_key: &impl PrivateScalar<N>,
+        nonce: NonceMode,
+    ) -> Result<([u8; N], [u8; N]), CracenError> {
+        let curve = PkCurve::with_op_size(N).ok_or(CracenError::InvalidInput)?;
+        let alg = prehash_alg(digest)?;
+        self.ecdsa_sign_hashed(curve, alg, digest, priv_key.scalar(), nonce)
+            .await
+    }
+
+    // The RFC 6979 nonce is derived under the hash lock, after any TRNG bytes
+    // for hedging have been drawn
+    async fn ecdsa_sign_hashed<const N: usize>(
+        &self,
+        curve: PkCurve,
+        alg: HashAlg,
+        digest: &[u8],
+        priv_key: &[u8; N],
+        nonce: NonceMode,
+    ) -> Result<([u8; N], [u8; N]), CracenError> {
+        let mut k = match nonce {
+            NonceMode::Random => {
+                // Redrawn until it lands in [1, n-1], like a private key
+                let mut k = [0u8; N];
+                loop {
+                    self.rng(&mut k).await?;
+                    curve.clamp_scalar(&mut k);
+                    if is_valid_nonce(curve, &k) {
+                        break k;
+                    }
+                }
+            }
+            NonceMode::Deterministic | NonceMode::Hedged => {
+                let mut extra = [0u8; HEDGE_ENTROPY_LEN];
+                let extra_len = if nonce == NonceMode::Hedged {
+                    self.rng(&mut extra).await?;
+                    HEDGE_ENTROPY_LEN
+                } else {
+                    0
+                };
+                let k = self.hash.lock().await.rfc6979_nonce(
+                    curve,
+                    alg,
+                    priv_key,
+                    digest,
+                    &extra[..extra_len],
+                );
+                wipe(&mut extra);
+                k?
+            }
+        };
+
+        let result = self
+            .pke
+            .lock()
+            .await
+            .ecdsa_sign_digest(curve, digest, &k, priv_key);
+        wipe(&mut k);
+        result
+    }
+
+    /// Same as [`Cracen::ecdsa_verify`].
+    pub async fn ecdsa_verify<const N: usize>(
+        &self,
+        message: &[u8],
+        sig_r: &[u8; N],
+        sig_s: &[u8; N],
+        pk_x: &[u8; N],
+        pk_y: &[u8; N],
+    ) -> Result<(), CracenError> {
+        let curve = PkCurve::with_op_size(N).ok_or(CracenError::InvalidInput)?;
+        self.ecdsa_verify_with(curve.hash_alg(), message, sig_r, sig_s, pk_x, pk_y)
+            .await
+    }
+
+    /// Same as [`Cracen::ecdsa_verify_with`].
+    pub async fn ecdsa_verify_with<const N: usize>(
+        &self,
+        alg: HashAlg,
+        message: &[u8],
+        sig_r: &[u8; N],
+        sig_s: &[u8; N],
+        pk_x: &[u8; N],
+        pk_y: &[u8; N],
+    ) -> Result<(), CracenError> {
+        let curve = PkCurve::with_op_size(N).ok_or(CracenError::InvalidInput)?;
+        let mut digest = [0u8; 64];
+        let digest = self.ecdsa_hash(alg, message, &mut digest).await?;
+
+        self.pke
+            .lock()
+            .await
+            .ecdsa_verify_digest(curve, digest, sig_r, sig_s, pk_x, pk_y)
+    }
+
+    /// Same as [`Cracen::ecdsa_verify_prehash`].
+    pub async fn ecdsa_verify_prehash<const N: usize>(
+        &self,
+        digest: &[u8],
+        sig_r: &[u8; N],
+        sig_s: &[u8; N],
+        pk_x: &[u8; N],
+        pk_y: &[u8; N],
+    ) -> Result<(), CracenError> {
+        let curve = PkCurve::with_op_size(N).ok_or(CracenError::InvalidInput)?;
+        prehash_alg(digest)?;
+
+        self.pke
+            .lock()
+            .await
+            .ecdsa_verify_digest(curve, digest, sig_r, sig_s, pk_x, pk_y)
+    }
+
+    pub async fn ec_scalar_mul<const N: usize>(
+        &self,
+        k: &[u8; N],
+        px: &[u8; N],
+        py: &[u8; N],
+        out_x: &mut [u8; N],
+        out_y: &mut [u8; N],
+    ) -> Result<(), CracenError> {
+        self.pke.lock().await.ec_scalar_mul(k, px, py, out_x, out_y)
+    }
+
+    /// Same as [`Cracen::ecdh_p256`].
+    pub async fn ecdh_p256(
+        &self,
+        priv_key: &impl PrivateScalar<32>,
+        peer_x: &[u8; 32],
+        peer_y: &[u8; 32],
+    ) -> Result<SharedSecret, CracenError> {
+        self.pke.lock().await.ecdh_p256(priv_key, peer_x, peer_y)
+    }
+
+    pub async fn x25519(&self, scalar: &[u8; 32], u: &[u8; 32]) -> Result<[u8; 32], CracenError> {
+        self.pke.lock().await.x25519(scalar, u)
+    }
+
+    pub async fn x25519_base(&self, scalar: &[u8; 32]) -> Result<[u8; 32], CracenError> {
+        self.pke.lock().await.x25519_base(scalar)
+    }
+
+    async fn ecdsa_hash<'d>(
+        &self,
+        alg: HashAlg,
+        msg: &[u8],
+        digest: &'d mut [u8; 64],
+    ) -> Result<&'d [u8], CracenError> {
+        match hash_out_len(alg) {
+            20 => {
+                self.hash(msg, digest.first_chunk_mut::<20>().unwrap(), alg)
+                    .await?
+            }
+            28 => {
+                self.hash(msg, digest.first_chunk_mut::<28>().unwrap(), alg)
+                    .await?
+            }
+            32 => {
+                self.hash(msg, digest.first_chunk_mut::<32>().unwrap(), alg)
+                    .await?
+            }
+            48 => {
+                self.hash(msg, digest.first_chunk_mut::<48>().unwrap(), alg)
+                    .await?
+            }
+            _ => self.hash(msg, digest, alg).await?,
+        }
+        Ok(&digest[..hash_out_len(alg)])
+    }
+}
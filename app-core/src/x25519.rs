@@ -0,0 +1,110 @@
+//! X25519 (RFC 7748) on the PK engine's Montgomery ladder.
+//!
+//! RFC 7748 encodes scalars and u-coordinates little-endian, while the PK
+//! engine takes big-endian operands, so both are reversed on the way in and
+//! the result on the way out.
+
+use crate::{Cracen, CracenError, PkCommand, PkCurve, Slot, wipe};
+
+/// The u-coordinate of the Curve25519 base point.
+pub const X25519_BASE_POINT: [u8; 32] = {
+    let mut u = [0u8; 32];
+    u[0] = 9;
+    u
+};
+
+// p = 2^255 - 19, little-endian
+const P: [u8; 32] = {
+    let mut p = [0xff; 32];
+    p[0] = 0xed;
+    p[31] = 0x7f;
+    p
+};
+
+// Reduces a u-coordinate with the top bit already masked modulo p. Only
+// p..2^255 are out of range, so one subtraction is enough. Both results are
+// computed and one picked with a mask, to not branch on the peer's key.
+fn reduce_u(u: &mut [u8; 32]) {
+    let mut diff = [0u8; 32];
+    let mut borrow = 0i16;
+    for i in 0..32 {
+        let d = u[i] as i16 - P[i] as i16 - borrow;
+        diff[i] = d as u8;
+        borrow = (d >> 8) & 1;
+    }
+    // 0xff if u >= p, i.e. the subtraction didn't borrow
+    let mask = (borrow as u8).wrapping_sub(1);
+    for i in 0..32 {
+        u[i] = (diff[i] & mask) | (u[i] & !mask);
+    }
+}
+
+impl Cracen {
+    /// Computes X25519(`scalar`, `u`), clamping the scalar, masking the top
+    /// bit of `u` and reducing a non-canonical `u` (2^255-19 and up) modulo p
+    /// as RFC 7748 section 5 asks.
+    ///
+    /// The result is not checked for the all-zero value a small-order `u`
+    /// produces; protocols that care have to compare it themselves.
+    pub fn x25519(&mut self, scalar: &[u8; 32], u: &[u8; 32]) -> Result<[u8; 32], CracenError> {
+        let mut k = *scalar;
+        k[0] &= 248;
+        k[31] &= 127;
+        k[31] |= 64;
+        k.reverse();
+
+        let mut u = *u;
+        u[31] &= 127;
+        reduce_u(&mut u);
+        u.reverse();
+
+        let mut job = self.pk_job(PkCommand::MontgomeryMul, PkCurve::Curve25519)?;
+        job.write(Slot::new(8), &k)?;
+        job.write(Slot::new(12), &u)?;
+        let result = job.run();
+        wipe(&mut k);
+        result?;
+
+        let mut out = [0u8; 32];
+        job.read(Slot::new(10), &mut out)?;
+        out.reverse();
+        Ok(out)
+    }
+
+    /// The X25519 public key for `scalar`, i.e. X25519(`scalar`, 9).
+    pub fn x25519_base(&mut self, scalar: &[u8; 32]) -> Result<[u8; 32], CracenError> {
+        self.x25519(scalar, &X25519_BASE_POINT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn le(low: u8, high: u8) -> [u8; 32] {
+        let mut u = [0xff; 32];
+        u[0] = low;
+        u[31] = high;
+        u
+    }
+
+    #[test]
+    fn reduce_u_non_canonical() {
+        let mut small = [0u8; 32];
+        for (low, reduced) in [(0xed, 0), (0xee, 1), (0xf6, 9), (0xff, 18)] {
+            let mut u = le(low, 0x7f);
+            reduce_u(&mut u);
+            small[0] = reduced;
+            assert_eq!(u, small);
+        }
+    }
+
+    #[test]
+    fn reduce_u_canonical() {
+        for u in [le(0xec, 0x7f), le(0xff, 0x7e), X25519_BASE_POINT, [0; 32]] {
+            let mut reduced = u;
+            reduce_u(&mut reduced);
+            assert_eq!(reduced, u);
+        }
+    }
+}